@@ -1,27 +1,6 @@
 use crate::prelude::*;
 use thiserror::Error as ThisError;
 
-#[derive(Debug, PartialEq, Eq, Clone, ThisError, Error)]
-pub enum FFISideError {
-    #[error(transparent)]
-    Networking {
-        #[from]
-        error: FFINetworkingError,
-    },
-
-    #[error(transparent)]
-    FileIOWrite {
-        #[from]
-        error: FFIFileIOWriteError,
-    },
-
-    #[error(transparent)]
-    FileIORead {
-        #[from]
-        error: FFIFileIOReadError,
-    },
-}
-
 #[derive(Debug, PartialEq, Eq, Clone, ThisError, Error)]
 pub enum RustSideError {
     #[error("No response code")]
@@ -47,19 +26,157 @@ pub enum RustSideError {
 
     #[error("Wrong response kind from FFIOperationOk, expected FFINetworkingResponse")]
     WrongFFIOperationOKExpectedFFINetworkingResponse,
+
+    #[error("FFI operation '{operation}' timed out after {elapsed_ms} ms")]
+    OperationTimedOut { operation: String, elapsed_ms: u64 },
+
+    #[error(
+        "Batch of FFI operation '{operation}' notified {actual} outcomes, expected {expected}"
+    )]
+    BatchOutcomeCountMismatch {
+        operation: String,
+        expected: u64,
+        actual: u64,
+    },
+
+    #[error("Blocking call made from within an async context, use the async variant instead")]
+    BlockingCallInAsyncContext,
+
+    #[error("Executor does not support the request: {reason}")]
+    UnsupportedByExecutor { reason: String },
+
+    #[error("Failed to load cassette at '{path}': {underlying}")]
+    FailedToLoadCassette { path: String, underlying: String },
+
+    #[error("No interaction recorded in cassette for '{method} {url}'")]
+    NoRecordedInteraction { method: String, url: String },
+
+    #[error("Circuit of FFI operation '{operation}' is open, failing fast, retry after {retry_after_ms} ms")]
+    CircuitOpen {
+        operation: String,
+        retry_after_ms: u64,
+    },
+
+    #[error("FFI side dropped the listener of FFI operation '{operation}' #{operation_id} without notifying the outcome, {elapsed_ms} ms after it was dispatched")]
+    ListenerDroppedWithoutOutcome {
+        operation: String,
+        operation_id: u64,
+        elapsed_ms: u64,
+    },
+}
+
+/// Reasons FFI side (Swift side) might fail to notify Rust side about the
+/// outcome of an operation, using `notify_outcome`, or about the values of a
+/// stream operation.
+#[derive(Debug, PartialEq, Eq, Clone, ThisError, Error)]
+pub enum NotifyOutcomeError {
+    #[error("Outcome of FFI operation '{operation}' #{operation_id} has already been notified, `notify_outcome` MUST only be called once")]
+    AlreadyNotified {
+        operation: String,
+        operation_id: u64,
+    },
+
+    #[error("Rust side is no longer awaiting the outcome of FFI operation '{operation}' #{operation_id}")]
+    NoLongerAwaited {
+        operation: String,
+        operation_id: u64,
+    },
+
+    #[error("Rust side has no room for more values of the stream of FFI operation '{operation}' #{operation_id}, notify the value again later or use `notify_value_async`")]
+    StreamBufferFull {
+        operation: String,
+        operation_id: u64,
+    },
+
+    #[error("Stream of FFI operation '{operation}' #{operation_id} has already finished, nothing can be notified after `notify_completed` or `notify_failed`")]
+    StreamAlreadyFinished {
+        operation: String,
+        operation_id: u64,
+    },
 }
 
+/// An error of an operation bridged to FFI side (Swift side), which originated
+/// either Rust side or FFI side. Errors produced by dispatching an operation
+/// carry the ID of the operation, the same ID FFI side can read from the
+/// request and the listener, so that both sides can correlate their logs.
 #[derive(Debug, PartialEq, Eq, Clone, ThisError, Error)]
 pub enum FFIBridgeError {
-    #[error(transparent)]
+    #[error("{error}")]
     FromRust {
-        #[from]
         error: RustSideError,
+        operation_id: Option<u64>,
     },
 
-    #[error(transparent)]
+    #[error("{error}")]
     FromFFI {
-        #[from]
         error: FFISideError,
+        operation_id: Option<u64>,
     },
 }
+
+impl From<RustSideError> for FFIBridgeError {
+    fn from(error: RustSideError) -> Self {
+        Self::FromRust {
+            error,
+            operation_id: None,
+        }
+    }
+}
+
+impl From<FFISideError> for FFIBridgeError {
+    fn from(error: FFISideError) -> Self {
+        Self::FromFFI {
+            error,
+            operation_id: None,
+        }
+    }
+}
+
+impl FFIBridgeError {
+    /// The ID of the operation which failed with this error, if any.
+    pub fn operation_id(&self) -> Option<u64> {
+        match self {
+            Self::FromRust { operation_id, .. } | Self::FromFFI { operation_id, .. } => {
+                *operation_id
+            }
+        }
+    }
+
+    /// Attaches `id` of the operation which failed with this error.
+    pub(crate) fn with_operation_id(mut self, id: u64) -> Self {
+        match &mut self {
+            Self::FromRust { operation_id, .. } | Self::FromFFI { operation_id, .. } => {
+                *operation_id = Some(id)
+            }
+        }
+        self
+    }
+}
+
+impl RustSideError {
+    /// Whether this error is due to a bug FFI side, reported to the installed
+    /// [`FFIHostBugHook`], if any.
+    pub(crate) fn is_host_bug(&self) -> bool {
+        matches!(self, Self::ListenerDroppedWithoutOutcome { .. })
+    }
+}
+
+impl IsRetryable for RustSideError {
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::FailedToReceiveResponseFromSwift
+                | Self::ListenerDroppedWithoutOutcome { .. }
+                | Self::OperationTimedOut { .. }
+        )
+    }
+}
+
+impl IsRetryable for FFIBridgeError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::FromRust { error, .. } => error.is_retryable(),
+            Self::FromFFI { error, .. } => error.is_retryable(),
+        }
+    }
+}
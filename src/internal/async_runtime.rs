@@ -0,0 +1,44 @@
+use crate::prelude::*;
+use futures_util::future::{select, Either};
+
+/// The error of a future which did not complete before its deadline.
+#[derive(Debug)]
+pub(crate) struct Elapsed;
+
+/// Requires `future` to complete before `deadline`.
+///
+/// Futures of exported `async` functions are polled by FFI side's (Swift side)
+/// executor, or any other executor embedding this crate, so timers are driven
+/// by a thread of their own, which works regardless of which executor polls
+/// the returned future.
+pub(crate) async fn timeout_at<F: Future>(
+    deadline: Instant,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    let future = std::pin::pin!(future);
+    let delay = futures_timer::Delay::new(deadline.saturating_duration_since(Instant::now()));
+    match select(future, delay).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(Elapsed),
+    }
+}
+
+/// Waits until `duration` has elapsed, like [`timeout_at`] regardless of
+/// which executor polls the returned future.
+pub(crate) fn sleep(duration: Duration) -> futures_timer::Delay {
+    futures_timer::Delay::new(duration)
+}
+
+/// Blocks the calling thread until `future` has completed, polling it on the
+/// calling thread, for FFI side without async bindings, e.g. CLI tools or
+/// legacy Java/Objective-C call sites.
+///
+/// Fails instead of blocking a worker thread, or deadlocking, if called from
+/// within a tokio async context.
+pub(crate) fn block_on<F: Future>(future: F) -> Result<F::Output, RustSideError> {
+    #[cfg(feature = "tokio")]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return Err(RustSideError::BlockingCallInAsyncContext);
+    }
+    Ok(futures_executor::block_on(future))
+}
@@ -0,0 +1,253 @@
+use crate::prelude::*;
+use std::collections::VecDeque;
+
+/// Policy of a circuit breaker, which stops a [`FFIOperationDispatcher`] from
+/// dispatching operations to an executor FFI side (Swift side) which keeps
+/// failing, e.g. while the host is offline, failing fast with
+/// [`RustSideError::CircuitOpen`] instead. Only [`IsRetryable`] failures,
+/// e.g. timeouts or HTTP 503, count as failures.
+#[derive(Record, Clone, Debug, PartialEq)]
+pub struct CircuitBreakerPolicy {
+    /// Fraction, between `0.0` and `1.0`, of failed operations within the
+    /// window at which the circuit opens.
+    pub failure_rate_threshold: f64,
+
+    /// Number of most recent operations the failure rate is computed over.
+    pub window_size: u32,
+
+    /// Minimum number of operations within the window before the circuit
+    /// can open, so that a single failure does not open it.
+    pub minimum_operations: u32,
+
+    /// Milliseconds the circuit stays open before letting trial operations
+    /// through, i.e. becoming half-open.
+    pub open_duration_ms: u64,
+
+    /// Number of trial operations let through while half-open, all of which
+    /// must succeed for the circuit to close, any failure opens it again.
+    pub half_open_trial_operations: u32,
+}
+
+impl Default for CircuitBreakerPolicy {
+    fn default() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            window_size: 20,
+            minimum_operations: 10,
+            open_duration_ms: 30_000,
+            half_open_trial_operations: 1,
+        }
+    }
+}
+
+/// The state of the circuit breaker of a [`FFIOperationDispatcher`], e.g. for
+/// FFI side (Swift side) to show an offline banner while it is not closed.
+#[derive(Enum, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CircuitState {
+    /// Operations are dispatched.
+    #[default]
+    Closed,
+
+    /// Operations fail fast with [`RustSideError::CircuitOpen`].
+    Open,
+
+    /// Trial operations are dispatched to find out whether the executor has
+    /// recovered, further operations fail fast until they have finished.
+    HalfOpen,
+}
+
+/// Opens the circuit of a [`FFIOperationDispatcher`] according to a
+/// [`CircuitBreakerPolicy`], if any, else the circuit is always closed.
+pub(crate) struct CircuitBreaker {
+    policy: Option<CircuitBreakerPolicy>,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Default)]
+struct BreakerState {
+    circuit: Circuit,
+    /// Incremented every time the circuit changes, so that the results of
+    /// operations let through before the change are ignored.
+    generation: u64,
+}
+
+enum Circuit {
+    /// The most recent results, `true` if failed.
+    Closed {
+        window: VecDeque<bool>,
+    },
+    Open {
+        until: Instant,
+    },
+    HalfOpen {
+        in_flight: u32,
+        succeeded: u32,
+    },
+}
+
+impl Default for Circuit {
+    fn default() -> Self {
+        Self::Closed {
+            window: VecDeque::new(),
+        }
+    }
+}
+
+impl BreakerState {
+    fn transition(&mut self, circuit: Circuit) {
+        self.circuit = circuit;
+        self.generation += 1;
+        match self.circuit {
+            Circuit::Closed { .. } => info!("Circuit closed"),
+            Circuit::Open { .. } => warn!("Circuit opened"),
+            Circuit::HalfOpen { .. } => info!("Circuit half-open"),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(policy: Option<CircuitBreakerPolicy>) -> Self {
+        Self {
+            policy,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, BreakerState> {
+        lock_unpoisoned(&self.state)
+    }
+
+    /// The current state of the circuit, an open circuit whose open duration
+    /// has elapsed counts as half-open.
+    pub(crate) fn circuit_state(&self) -> CircuitState {
+        match self.state().circuit {
+            Circuit::Closed { .. } => CircuitState::Closed,
+            Circuit::Open { until } if Instant::now() < until => CircuitState::Open,
+            Circuit::Open { .. } | Circuit::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Lets an `operation` through, unless the circuit is open, or half-open
+    /// with all trial operations already in flight. The result of the
+    /// operation must be recorded with the returned [`CircuitPermit`].
+    pub(crate) fn try_acquire(&self, operation: &str) -> Result<CircuitPermit<'_>, RustSideError> {
+        let Some(policy) = self.policy.as_ref() else {
+            return Ok(CircuitPermit::new(self, None));
+        };
+        let mut state = self.state();
+        if let Circuit::Open { until } = state.circuit {
+            let now = Instant::now();
+            if now < until {
+                return Err(RustSideError::CircuitOpen {
+                    operation: operation.to_owned(),
+                    retry_after_ms: until.duration_since(now).as_millis() as u64,
+                });
+            }
+            state.transition(Circuit::HalfOpen {
+                in_flight: 0,
+                succeeded: 0,
+            });
+        }
+        let generation = state.generation;
+        match &mut state.circuit {
+            Circuit::Closed { .. } => Ok(CircuitPermit::new(self, Some(generation))),
+            Circuit::HalfOpen {
+                in_flight,
+                succeeded,
+            } => {
+                if *in_flight + *succeeded >= policy.half_open_trial_operations.max(1) {
+                    return Err(RustSideError::CircuitOpen {
+                        operation: operation.to_owned(),
+                        retry_after_ms: 0,
+                    });
+                }
+                *in_flight += 1;
+                Ok(CircuitPermit::new(self, Some(generation)))
+            }
+            Circuit::Open { .. } => unreachable!("An elapsed open circuit is half-open"),
+        }
+    }
+
+    fn record(&self, generation: u64, failed: Option<bool>) {
+        let Some(policy) = self.policy.as_ref() else {
+            return;
+        };
+        let mut state = self.state();
+        if state.generation != generation {
+            return;
+        }
+        let open = || Circuit::Open {
+            until: Instant::now() + Duration::from_millis(policy.open_duration_ms),
+        };
+        let next = match &mut state.circuit {
+            Circuit::Closed { window } => {
+                let Some(failed) = failed else {
+                    return;
+                };
+                window.push_back(failed);
+                while window.len() > policy.window_size.max(1) as usize {
+                    window.pop_front();
+                }
+                let failures = window.iter().filter(|failed| **failed).count();
+                (failures > 0
+                    && window.len() >= policy.minimum_operations.max(1) as usize
+                    && failures as f64 >= policy.failure_rate_threshold * window.len() as f64)
+                    .then(open)
+            }
+            Circuit::HalfOpen {
+                in_flight,
+                succeeded,
+            } => {
+                *in_flight -= 1;
+                match failed {
+                    Some(true) => Some(open()),
+                    Some(false) => {
+                        *succeeded += 1;
+                        (*succeeded >= policy.half_open_trial_operations.max(1))
+                            .then(Circuit::default)
+                    }
+                    // The trial was cancelled, let another one through.
+                    None => None,
+                }
+            }
+            Circuit::Open { .. } => None,
+        };
+        if let Some(circuit) = next {
+            state.transition(circuit)
+        }
+    }
+}
+
+/// Lets an operation through a [`CircuitBreaker`], dropping it without
+/// recording a result, e.g. when the operation is cancelled, records nothing.
+pub(crate) struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    /// `None` once recorded, or if the breaker has no policy.
+    generation: Option<u64>,
+}
+
+impl<'a> CircuitPermit<'a> {
+    fn new(breaker: &'a CircuitBreaker, generation: Option<u64>) -> Self {
+        Self {
+            breaker,
+            generation,
+        }
+    }
+
+    /// Records the `result` of the operation, only [`IsRetryable`] failures
+    /// count as failures.
+    pub(crate) fn record<T>(mut self, result: &Result<T, FFIBridgeError>) {
+        if let Some(generation) = self.generation.take() {
+            let failed = result.as_ref().is_err_and(IsRetryable::is_retryable);
+            self.breaker.record(generation, Some(failed))
+        }
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if let Some(generation) = self.generation.take() {
+            self.breaker.record(generation, None)
+        }
+    }
+}
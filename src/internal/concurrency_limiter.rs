@@ -0,0 +1,155 @@
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// The priority of an operation queued by a [`ConcurrencyLimiter`], operations
+/// with higher priority are dispatched first, operations with the same
+/// priority in the order they were queued.
+#[derive(Enum, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OperationPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// A semaphore limiting the number of operations a [`FFIOperationDispatcher`]
+/// has in flight FFI side (Swift side), queueing any further operations by
+/// [`OperationPriority`] until an operation in flight has finished.
+pub(crate) struct ConcurrencyLimiter {
+    /// If `None` operations are never queued.
+    max_in_flight: Option<usize>,
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Default)]
+struct LimiterState {
+    in_flight: usize,
+    queue: BinaryHeap<QueuedOperation>,
+    next_sequence_number: u64,
+}
+
+struct QueuedOperation {
+    priority: OperationPriority,
+    sequence_number: u64,
+    sender: Sender<()>,
+}
+
+impl Ord for QueuedOperation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Highest priority first, then first in first out.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence_number.cmp(&self.sequence_number))
+    }
+}
+
+impl PartialOrd for QueuedOperation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedOperation {
+    fn eq(&self, other: &Self) -> bool {
+        self.sequence_number == other.sequence_number
+    }
+}
+
+impl Eq for QueuedOperation {}
+
+impl ConcurrencyLimiter {
+    pub(crate) fn new(max_in_flight: Option<usize>) -> Self {
+        Self {
+            max_in_flight: max_in_flight.map(|max| max.max(1)),
+            state: Mutex::new(LimiterState::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, LimiterState> {
+        lock_unpoisoned(&self.state)
+    }
+
+    /// The number of operations currently in flight.
+    pub(crate) fn in_flight(&self) -> usize {
+        self.state().in_flight
+    }
+
+    /// The number of operations waiting for an operation in flight to finish.
+    pub(crate) fn queue_depth(&self) -> usize {
+        self.state()
+            .queue
+            .iter()
+            .filter(|queued| !queued.sender.is_canceled())
+            .count()
+    }
+
+    /// Waits until fewer than max operations are in flight, the returned
+    /// [`ConcurrencyPermit`] counts as in flight until dropped. Dropping the
+    /// returned future removes the operation from the queue.
+    pub(crate) async fn acquire(&self, priority: OperationPriority) -> ConcurrencyPermit<'_> {
+        let receiver = {
+            let mut state = self.state();
+            if self.max_in_flight.map_or(true, |max| state.in_flight < max) {
+                state.in_flight += 1;
+                return ConcurrencyPermit { limiter: self };
+            }
+            let (sender, receiver) = channel();
+            let sequence_number = state.next_sequence_number;
+            state.next_sequence_number += 1;
+            state.queue.push(QueuedOperation {
+                priority,
+                sequence_number,
+                sender,
+            });
+            receiver
+        };
+
+        let mut queued = QueuedReceiver {
+            limiter: self,
+            receiver,
+        };
+        // `release` never drops the sender of a queued operation without
+        // handing over its permit.
+        let _ = (&mut queued.receiver).await;
+        ConcurrencyPermit { limiter: self }
+    }
+
+    /// Hands over the permit of a finished operation to the queued operation
+    /// with the highest priority, if any.
+    fn release(&self) {
+        let mut state = self.state();
+        while let Some(queued) = state.queue.pop() {
+            if queued.sender.send(()).is_ok() {
+                return;
+            }
+        }
+        state.in_flight -= 1;
+    }
+}
+
+/// A permit of an operation in flight, acquired from a [`ConcurrencyLimiter`].
+pub(crate) struct ConcurrencyPermit<'a> {
+    limiter: &'a ConcurrencyLimiter,
+}
+
+impl Drop for ConcurrencyPermit<'_> {
+    fn drop(&mut self) {
+        self.limiter.release()
+    }
+}
+
+/// Releases the permit handed over to a queued operation whose future was
+/// dropped before it could be turned into a [`ConcurrencyPermit`].
+struct QueuedReceiver<'a> {
+    limiter: &'a ConcurrencyLimiter,
+    receiver: futures_channel::oneshot::Receiver<()>,
+}
+
+impl Drop for QueuedReceiver<'_> {
+    fn drop(&mut self) {
+        if let Ok(Some(())) = self.receiver.try_recv() {
+            self.limiter.release()
+        }
+    }
+}
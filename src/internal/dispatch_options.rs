@@ -0,0 +1,32 @@
+use crate::prelude::*;
+
+/// Options of a single dispatch of an operation by a [`FFIOperationDispatcher`],
+/// overriding or complementing its [`DispatcherConfiguration`].
+#[derive(Clone, Default)]
+pub(crate) struct DispatchOptions {
+    /// Await the outcome until this instant at the latest, any retries
+    /// included, while the default timeout of the dispatcher still applies to
+    /// every attempt.
+    pub(crate) deadline: Option<Instant>,
+
+    /// Observes progress FFI side notifies Rust side about, restarting for
+    /// every retry of the operation.
+    pub(crate) progress_observer: Option<Arc<dyn FFIOperationProgressObserver>>,
+
+    /// The priority of the operation if it is queued, because the dispatcher
+    /// has max operations in flight.
+    pub(crate) priority: OperationPriority,
+
+    /// The ID of the operation, if the caller has already generated it, e.g.
+    /// to attach it to errors produced before or after dispatching it.
+    pub(crate) operation_id: Option<u64>,
+}
+
+impl DispatchOptions {
+    pub(crate) fn with_deadline(deadline: Option<Instant>) -> Self {
+        Self {
+            deadline,
+            ..Self::default()
+        }
+    }
+}
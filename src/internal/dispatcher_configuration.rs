@@ -0,0 +1,62 @@
+use crate::prelude::*;
+
+/// Configuration of how a [`FFIOperationDispatcher`] dispatches operations to
+/// FFI side (Swift side).
+#[derive(Record, Clone, Debug, Default, PartialEq)]
+pub struct DispatcherConfiguration {
+    /// Number of milliseconds Rust side awaits the outcome of an operation
+    /// before failing with [`RustSideError::OperationTimedOut`], if `None` Rust
+    /// side awaits the outcome forever.
+    #[uniffi(default = None)]
+    pub timeout_ms: Option<u64>,
+
+    /// If set, operations failing with a retryable failure, e.g. HTTP 503
+    /// or FFI side dropping the listener, are retried according to the policy.
+    /// The timeout applies to each attempt.
+    #[uniffi(default = None)]
+    pub retry_policy: Option<RetryPolicy>,
+
+    /// The max number of operations Rust side has in flight FFI side at any
+    /// time, further operations are queued until an operation in flight has
+    /// finished, if `None` operations are never queued.
+    #[uniffi(default = None)]
+    pub max_in_flight: Option<u32>,
+
+    /// If `true`, an operation dispatched while an identical operation with
+    /// the same priority is in flight, e.g. a HTTP request with the same url,
+    /// method and body, shares its execution FFI side and receives a clone of
+    /// the outcome FFI side notifies, awaiting it until its own deadline. If
+    /// the operation in flight fails Rust side instead, e.g. times out, the
+    /// operation is dispatched on its own. Operations observing their progress
+    /// are never coalesced.
+    #[uniffi(default = false)]
+    pub coalesce_requests: bool,
+
+    /// If set, operations fail fast with [`RustSideError::CircuitOpen`] while
+    /// too many recent operations have failed, e.g. while the host is offline,
+    /// instead of being dispatched to FFI side only to fail slowly.
+    #[uniffi(default = None)]
+    pub circuit_breaker: Option<CircuitBreakerPolicy>,
+
+    /// The kinds of failures upon which an operation is passed on to the next
+    /// executor, if the dispatcher has a chain of executors. If `None`,
+    /// operations fall back upon [`FallbackTrigger::RetryableFailure`]s.
+    #[uniffi(default = None)]
+    pub fallback_on: Option<Vec<FallbackTrigger>>,
+}
+
+impl DispatcherConfiguration {
+    pub(crate) fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    /// Whether an operation which failed with `error` should be passed on to
+    /// the next executor, if any.
+    pub(crate) fn falls_back_on(&self, error: &FFIBridgeError) -> bool {
+        self.fallback_on
+            .as_deref()
+            .unwrap_or(&FallbackTrigger::DEFAULT)
+            .iter()
+            .any(|trigger| trigger.is_triggered_by(error))
+    }
+}
@@ -0,0 +1,167 @@
+use crate::prelude::*;
+
+/// Upper bounds, in milliseconds, of the buckets of [`LatencyHistogram`]s,
+/// followed by a bucket without upper bound.
+const LATENCY_BUCKET_UPPER_BOUNDS_MS: [u64; 10] =
+    [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// A snapshot of what a [`FFIOperationDispatcher`] has dispatched to FFI side
+/// (Swift side) and how it went, e.g. for a diagnostics screen. Every attempt
+/// of an operation counts as a dispatched operation.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct DispatcherStats {
+    /// Number of operations passed to the executor FFI side.
+    pub dispatched: u64,
+
+    /// Number of operations FFI side notified a successful outcome of.
+    pub succeeded: u64,
+
+    /// Number of operations which failed FFI side, either the executor threw
+    /// or FFI side notified a failure outcome.
+    pub host_failed: u64,
+
+    /// Number of operations which failed Rust side, apart from timeouts and
+    /// operations never notified about.
+    pub rust_failed: u64,
+
+    /// Number of operations which timed out, either in flight, queued or
+    /// awaiting the outcome of an identical operation they were coalesced into.
+    pub timed_out: u64,
+
+    /// Number of operations FFI side dropped the listener of without
+    /// notifying the outcome.
+    pub never_notified: u64,
+
+    /// Number of operations currently in flight FFI side.
+    pub in_flight: u64,
+
+    /// Number of operations currently queued, waiting for operations in
+    /// flight to finish.
+    pub queue_depth: u64,
+
+    /// Latency of operations FFI side notified the outcome of, from passing
+    /// the request to the executor until the outcome was notified.
+    pub latency: LatencyHistogram,
+}
+
+/// A histogram of latencies of operations.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Buckets in ascending order, the last without upper bound.
+    pub buckets: Vec<LatencyBucket>,
+
+    /// Sum of all latencies, in milliseconds.
+    pub sum_ms: u64,
+
+    /// Max of all latencies, in milliseconds.
+    pub max_ms: u64,
+}
+
+/// A bucket of a [`LatencyHistogram`].
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct LatencyBucket {
+    /// Latencies counted by this bucket are at most this many milliseconds,
+    /// if `None` this bucket counts all latencies above the previous bucket.
+    pub upper_bound_ms: Option<u64>,
+
+    /// Number of operations with a latency within this bucket.
+    pub count: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            buckets: LATENCY_BUCKET_UPPER_BOUNDS_MS
+                .into_iter()
+                .map(Some)
+                .chain([None])
+                .map(|upper_bound_ms| LatencyBucket {
+                    upper_bound_ms,
+                    count: 0,
+                })
+                .collect(),
+            sum_ms: 0,
+            max_ms: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency: Duration) {
+        let latency_ms = latency.as_millis() as u64;
+        self.sum_ms += latency_ms;
+        self.max_ms = self.max_ms.max(latency_ms);
+        if let Some(bucket) = self.buckets.iter_mut().find(|bucket| {
+            bucket
+                .upper_bound_ms
+                .map_or(true, |upper_bound_ms| latency_ms <= upper_bound_ms)
+        }) {
+            bucket.count += 1
+        }
+    }
+}
+
+/// Records the [`DispatcherStats`] of a [`FFIOperationDispatcher`].
+#[derive(Default)]
+pub(crate) struct DispatcherMetrics {
+    stats: Mutex<RecordedStats>,
+}
+
+#[derive(Default)]
+struct RecordedStats {
+    dispatched: u64,
+    succeeded: u64,
+    host_failed: u64,
+    rust_failed: u64,
+    timed_out: u64,
+    never_notified: u64,
+    latency: LatencyHistogram,
+}
+
+impl DispatcherMetrics {
+    fn stats(&self) -> MutexGuard<'_, RecordedStats> {
+        lock_unpoisoned(&self.stats)
+    }
+
+    /// Records that an operation was passed to the executor.
+    pub(crate) fn record_dispatched(&self) {
+        self.stats().dispatched += 1
+    }
+
+    /// Records the `result` of an operation.
+    pub(crate) fn record_result<T>(&self, result: &Result<T, FFIBridgeError>) {
+        let mut stats = self.stats();
+        match result {
+            Ok(_) => stats.succeeded += 1,
+            Err(FFIBridgeError::FromFFI { .. }) => stats.host_failed += 1,
+            Err(FFIBridgeError::FromRust { error, .. }) => match error {
+                RustSideError::OperationTimedOut { .. } => stats.timed_out += 1,
+                RustSideError::FailedToReceiveResponseFromSwift
+                | RustSideError::ListenerDroppedWithoutOutcome { .. } => stats.never_notified += 1,
+                _ => stats.rust_failed += 1,
+            },
+        }
+    }
+
+    /// Records the `latency` of an operation FFI side notified the outcome of.
+    pub(crate) fn record_latency(&self, latency: Duration) {
+        self.stats().latency.record(latency)
+    }
+
+    /// A snapshot of the recorded stats, with the current number of
+    /// operations `in_flight` and queued.
+    pub(crate) fn snapshot(&self, in_flight: usize, queue_depth: usize) -> DispatcherStats {
+        let stats = self.stats();
+        DispatcherStats {
+            dispatched: stats.dispatched,
+            succeeded: stats.succeeded,
+            host_failed: stats.host_failed,
+            rust_failed: stats.rust_failed,
+            timed_out: stats.timed_out,
+            never_notified: stats.never_notified,
+            in_flight: in_flight as u64,
+            queue_depth: queue_depth as u64,
+            latency: stats.latency.clone(),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::prelude::*;
+use std::sync::RwLock;
+
+/// Holds the executor of a dispatcher, which FFI side (Swift side) can replace
+/// while operations are in flight, e.g. with a `URLSession` using another
+/// proxy after login. Operations already passed to the replaced executor
+/// finish on it, all operations dispatched afterwards use the new executor.
+pub(crate) struct ExecutorSlot<E: ?Sized> {
+    executor: RwLock<Arc<E>>,
+}
+
+impl<E: ?Sized> ExecutorSlot<E> {
+    pub(crate) fn new(executor: Arc<E>) -> Self {
+        Self {
+            executor: RwLock::new(executor),
+        }
+    }
+
+    /// The current executor.
+    pub(crate) fn load(&self) -> Arc<E> {
+        read_unpoisoned(&self.executor).clone()
+    }
+
+    /// Replaces the current executor with `executor`.
+    pub(crate) fn replace(&self, executor: Arc<E>) {
+        *write_unpoisoned(&self.executor) = executor;
+    }
+}
@@ -0,0 +1,50 @@
+use crate::prelude::*;
+
+/// A kind of failure upon which a [`FFIOperationDispatcher`] with a chain of
+/// executors passes the operation on to the next executor in the chain, e.g.
+/// from a `URLSession` to a proxy-backed one, or from an on-disk file reader
+/// to one reading bundled resources.
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FallbackTrigger {
+    /// Any failure which is [`IsRetryable`], e.g. HTTP 503 or a timeout.
+    RetryableFailure,
+
+    /// Any failure FFI side (Swift side), i.e. the executor threw or notified
+    /// a failure outcome.
+    HostFailure,
+
+    /// The operation timed out.
+    Timeout,
+
+    /// FFI side dropped the listener without notifying the outcome.
+    NeverNotified,
+}
+
+impl FallbackTrigger {
+    /// The triggers of a dispatcher which has not been configured with any.
+    pub(crate) const DEFAULT: [Self; 1] = [Self::RetryableFailure];
+
+    /// Whether an operation which failed with `error` should be passed on to
+    /// the next executor.
+    pub(crate) fn is_triggered_by(&self, error: &FFIBridgeError) -> bool {
+        match self {
+            Self::RetryableFailure => error.is_retryable(),
+            Self::HostFailure => matches!(error, FFIBridgeError::FromFFI { .. }),
+            Self::Timeout => matches!(
+                error,
+                FFIBridgeError::FromRust {
+                    error: RustSideError::OperationTimedOut { .. },
+                    ..
+                }
+            ),
+            Self::NeverNotified => matches!(
+                error,
+                FFIBridgeError::FromRust {
+                    error: RustSideError::FailedToReceiveResponseFromSwift
+                        | RustSideError::ListenerDroppedWithoutOutcome { .. },
+                    ..
+                }
+            ),
+        }
+    }
+}
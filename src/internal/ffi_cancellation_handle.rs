@@ -0,0 +1,122 @@
+use crate::prelude::*;
+
+/// Implemented FFI side (Swift side) to be notified when a
+/// [`FFICancellationHandle`] is cancelled, e.g. to cancel a `URLSessionTask`.
+#[uniffi::export(with_foreign)]
+pub trait FFICancellationObserver: Send + Sync {
+    fn on_cancelled(&self);
+}
+
+/// A handle shared between Rust side and FFI side (Swift side), which either
+/// side can cancel and the other side can observe.
+///
+/// Rust side passes one to the executor of every dispatched operation, and
+/// cancels it if the future awaiting the outcome of the operation is dropped,
+/// e.g. because the Swift `Task` awaiting it was cancelled, or times out.
+#[derive(Object)]
+pub struct FFICancellationHandle {
+    state: Mutex<CancellationState>,
+}
+
+#[derive(Default)]
+struct CancellationState {
+    is_cancelled: bool,
+    observers: Vec<Arc<dyn FFICancellationObserver>>,
+    waiters: Vec<Sender<()>>,
+}
+
+impl FFICancellationHandle {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(CancellationState::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, CancellationState> {
+        lock_unpoisoned(&self.state)
+    }
+
+    /// Completes once this handle has been cancelled, by either side.
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn cancelled(&self) {
+        let (sender, receiver) = channel::<()>();
+        {
+            let mut state = self.state();
+            if state.is_cancelled {
+                return;
+            }
+            state.waiters.push(sender);
+        }
+        let _ = receiver.await;
+    }
+
+    /// Returns a guard which cancels this handle when dropped, unless
+    /// [`CancelOnDrop::disarm`] has been called.
+    pub(crate) fn cancel_on_drop(self: &Arc<Self>) -> CancelOnDrop {
+        CancelOnDrop {
+            handle: Some(self.clone()),
+        }
+    }
+}
+
+#[export]
+impl FFICancellationHandle {
+    /// Cancels this handle, notifying all observers. Calling it more than once
+    /// has no effect.
+    pub fn cancel(&self) {
+        let (observers, waiters) = {
+            let mut state = self.state();
+            if state.is_cancelled {
+                return;
+            }
+            state.is_cancelled = true;
+            (
+                std::mem::take(&mut state.observers),
+                std::mem::take(&mut state.waiters),
+            )
+        };
+        // Notify outside of lock, since observers might query this handle.
+        for waiter in waiters {
+            let _ = waiter.send(());
+        }
+        for observer in observers {
+            observer.on_cancelled();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state().is_cancelled
+    }
+
+    /// Registers `observer` to be notified when this handle is cancelled, if
+    /// it already is cancelled `observer` is notified immediately.
+    pub fn register_observer(&self, observer: Arc<dyn FFICancellationObserver>) {
+        {
+            let mut state = self.state();
+            if !state.is_cancelled {
+                state.observers.push(observer);
+                return;
+            }
+        }
+        observer.on_cancelled();
+    }
+}
+
+/// Cancels a [`FFICancellationHandle`] when dropped, unless disarmed.
+pub(crate) struct CancelOnDrop {
+    handle: Option<Arc<FFICancellationHandle>>,
+}
+
+impl CancelOnDrop {
+    pub(crate) fn disarm(mut self) {
+        self.handle = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.cancel()
+        }
+    }
+}
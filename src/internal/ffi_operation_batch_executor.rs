@@ -0,0 +1,13 @@
+use crate::prelude::*;
+
+/// An executor which can carry out many operations of the same kind in a
+/// single FFI crossing, e.g. read dozens of files at app launch, notifying
+/// Rust side about all their outcomes at once.
+pub trait FFIOperationBatchExecutor<L: IsOutcomeListener>: Send + Sync {
+    fn execute_batch_request(
+        &self,
+        requests: Vec<L::Request>,
+        listener_rust_side: L::BatchListener,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<(), FFISideError>;
+}
@@ -0,0 +1,27 @@
+use crate::prelude::*;
+
+/// The point in time at which Rust side stops awaiting the outcome of an
+/// operation dispatched to FFI side (Swift side), passed along with the request
+/// so that FFI side can set its own timeout too, e.g. `URLRequest.timeoutInterval`.
+#[derive(Record, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FFIOperationDeadline {
+    /// The deadline as milliseconds since UNIX epoch.
+    pub unix_timestamp_ms: u64,
+
+    /// Number of milliseconds left until the deadline, at the time of dispatch.
+    pub timeout_ms: u64,
+}
+
+impl FFIOperationDeadline {
+    pub(crate) fn new(deadline: Instant) -> Self {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        let unix_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            + timeout;
+        Self {
+            unix_timestamp_ms: unix_timestamp.as_millis() as u64,
+            timeout_ms: timeout.as_millis() as u64,
+        }
+    }
+}
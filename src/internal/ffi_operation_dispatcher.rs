@@ -1,39 +1,673 @@
 use crate::prelude::*;
+use futures_channel::mpsc;
+use futures_util::future::join_all;
 
-pub struct FFIOperationDispatcher<L: IsOutcomeListener> {
-    pub executor: Arc<dyn FFIOperationExecutor<L>>,
+/// The result of an operation, shared by identical operations coalesced into it.
+type ServedResult<L> = Result<Served<<L as IsOutcomeListener>::Response>, FFIBridgeError>;
+
+/// Dispatches operations to FFI side (Swift side), either operations
+/// producing a single outcome, if `L` is an [`IsOutcomeListener`], or stream
+/// operations producing any number of values, if `L` is an [`IsStreamListener`].
+pub struct FFIOperationDispatcher<L: IsListener> {
+    executor: ExecutorSlot<L::Executor>,
+    /// Executors operations are passed on to, in order, if the executor
+    /// before fails with a [`FallbackTrigger`] of the configuration.
+    fallback_executors: Vec<Arc<L::Executor>>,
+    pub configuration: DispatcherConfiguration,
+    concurrency_limiter: ConcurrencyLimiter,
+    circuit_breaker: CircuitBreaker,
+    /// Shared with the streams of stream operations, which finish after the
+    /// dispatch has returned.
+    metrics: Arc<DispatcherMetrics>,
+    state: L::DispatcherState,
+}
+
+/// What a dispatcher of operations producing a single outcome keeps apart
+/// from its executors.
+pub struct OutcomeDispatcherState<L: IsOutcomeListener> {
+    batch_executor: Mutex<Option<Arc<dyn FFIOperationBatchExecutor<L>>>>,
+    interceptors: Mutex<Vec<Arc<dyn FFIOperationInterceptor<L>>>>,
+    /// Only operations with the same priority are coalesced.
+    coalescer: RequestCoalescer<
+        (OperationPriority, <L::Request as IsCoalescableRequest>::Key),
+        ServedResult<L>,
+    >,
+}
+
+impl<L: IsOutcomeListener> Default for OutcomeDispatcherState<L> {
+    fn default() -> Self {
+        Self {
+            batch_executor: Mutex::new(None),
+            interceptors: Mutex::new(Vec::new()),
+            coalescer: RequestCoalescer::new(),
+        }
+    }
+}
+
+impl<L: IsListener> FFIOperationDispatcher<L> {
+    pub fn new(handler: Arc<L::Executor>) -> Self {
+        Self::with_configuration(handler, DispatcherConfiguration::default())
+    }
+
+    pub fn with_configuration(
+        handler: Arc<L::Executor>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self::with_fallback_executors(handler, Vec::new(), configuration)
+    }
+
+    /// A dispatcher passing operations to `handler` first, and on to the
+    /// `fallback_executors`, in order, whenever the executor before fails
+    /// with a failure the `configuration` falls back on.
+    pub fn with_fallback_executors(
+        handler: Arc<L::Executor>,
+        fallback_executors: Vec<Arc<L::Executor>>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self {
+            executor: ExecutorSlot::new(handler),
+            fallback_executors,
+            concurrency_limiter: ConcurrencyLimiter::new(
+                configuration.max_in_flight.map(|max| max as usize),
+            ),
+            circuit_breaker: CircuitBreaker::new(configuration.circuit_breaker.clone()),
+            configuration,
+            metrics: Arc::new(DispatcherMetrics::default()),
+            state: L::DispatcherState::default(),
+        }
+    }
+
+    /// The executor operations dispatched now are passed to.
+    pub fn executor(&self) -> Arc<L::Executor> {
+        self.executor.load()
+    }
+
+    /// The chain of executors of this dispatcher, starting with the executor
+    /// operations dispatched now are passed to first, followed by its
+    /// fallback executors, if any.
+    pub fn executors(&self) -> Vec<Arc<L::Executor>> {
+        std::iter::once(self.executor())
+            .chain(self.fallback_executors.iter().cloned())
+            .collect()
+    }
+
+    /// Replaces the executor of this dispatcher with `executor`, to which all
+    /// operations, and attempts of operations, dispatched after this call are
+    /// passed. Operations in flight finish on the replaced executor.
+    pub fn replace_executor(&self, executor: Arc<L::Executor>) {
+        self.executor.replace(executor)
+    }
+
+    /// A snapshot of the stats of all operations this dispatcher has
+    /// dispatched to FFI side.
+    pub fn stats(&self) -> DispatcherStats {
+        self.metrics.snapshot(
+            self.concurrency_limiter.in_flight(),
+            self.concurrency_limiter.queue_depth(),
+        )
+    }
+
+    /// The state of the circuit breaker of this dispatcher, always
+    /// [`CircuitState::Closed`] unless configured with a [`CircuitBreakerPolicy`].
+    pub fn circuit_state(&self) -> CircuitState {
+        self.circuit_breaker.circuit_state()
+    }
+
+    /// The number of operations queued because this dispatcher already has
+    /// max operations in flight FFI side.
+    pub fn queue_depth(&self) -> usize {
+        self.concurrency_limiter.queue_depth()
+    }
 }
 
 impl<L: IsOutcomeListener> FFIOperationDispatcher<L> {
-    pub fn new(handler: Arc<dyn FFIOperationExecutor<L>>) -> Self {
-        Self { executor: handler }
+    /// Adds `interceptor` last in the chain of interceptors of this dispatcher,
+    /// which inspect and modify every request and inspect every outcome of all
+    /// operations dispatched after this call.
+    pub fn add_interceptor(&self, interceptor: Arc<dyn FFIOperationInterceptor<L>>) {
+        self.lock_interceptors().push(interceptor)
+    }
+
+    /// Sets `batch_executor` to carry out all batches dispatched after this
+    /// call in a single FFI crossing each, instead of one crossing per request.
+    pub fn set_batch_executor(&self, batch_executor: Arc<dyn FFIOperationBatchExecutor<L>>) {
+        *lock_unpoisoned(&self.state.batch_executor) = Some(batch_executor)
     }
 
+    fn lock_interceptors(&self) -> MutexGuard<'_, Vec<Arc<dyn FFIOperationInterceptor<L>>>> {
+        lock_unpoisoned(&self.state.interceptors)
+    }
+
+    /// Dispatches `operation` to FFI side and awaits its outcome, using the
+    /// default timeout of this dispatcher, if any, for every attempt.
     pub(crate) async fn dispatch(
         &self,
         operation: L::Request,
     ) -> Result<L::Response, FFIBridgeError> {
-        // Underlying tokio channel used to get result from Swift back to Rust.
-        let (sender, receiver) = channel::<L::Outcome>();
+        self.dispatch_with_options(operation, DispatchOptions::default())
+            .await
+    }
+
+    /// Dispatches `operation` to FFI side and awaits its outcome according to
+    /// `options`, e.g. until its `deadline` at the latest, while the default
+    /// timeout of this dispatcher still applies to every attempt.
+    pub(crate) async fn dispatch_with_options(
+        &self,
+        operation: L::Request,
+        options: DispatchOptions,
+    ) -> Result<L::Response, FFIBridgeError> {
+        self.dispatch_served(operation, options)
+            .await
+            .map(|served| served.value)
+    }
+
+    /// Like [`FFIOperationDispatcher::dispatch_with_options`], but also
+    /// returns which executor of the chain of executors served `operation`.
+    pub(crate) async fn dispatch_served(
+        &self,
+        operation: L::Request,
+        options: DispatchOptions,
+    ) -> Result<Served<L::Response>, FFIBridgeError> {
+        // Identifies the operation in all attempts, on both sides, also if it
+        // is coalesced into an identical operation.
+        let operation_id = options.operation_id.unwrap_or_else(next_operation_id);
+        // Every attempt must finish within the timeout of this dispatcher, and
+        // all attempts together before the deadline of the call, if any.
+        let timeout = self.configuration.timeout();
+        // Progress is only reported to the observer of the operation executed,
+        // so operations observing their progress are never coalesced.
+        let coalescing_key =
+            if self.configuration.coalesce_requests && options.progress_observer.is_none() {
+                operation.coalescing_key()
+            } else {
+                None
+            };
+        let Some(key) = coalescing_key else {
+            return self
+                .dispatch_retrying(operation, operation_id, timeout, &options)
+                .await;
+        };
+
+        // Identical operations in flight share the outcome FFI side notified
+        // about the first one, a failure carrying the ID of that operation.
+        // If it failed Rust side instead, e.g. timed out under its own options,
+        // the others are dispatched again under theirs. Every operation awaits
+        // the shared outcome until its own deadline only.
+        let started_at = Instant::now();
+        let served = self.state.coalescer.coalesce(
+            (options.priority, key),
+            |result| matches!(result, Ok(_) | Err(FFIBridgeError::FromFFI { .. })),
+            || self.dispatch_retrying(operation, operation_id, timeout, &options),
+        );
+        let Some(deadline) = options.deadline else {
+            return served.await;
+        };
+        match timeout_at(deadline, served).await {
+            Ok(result) => result,
+            Err(_) => {
+                let error = FFIBridgeError::from(RustSideError::OperationTimedOut {
+                    operation: L::OPERATION_KIND.to_owned(),
+                    elapsed_ms: started_at.elapsed().as_millis() as u64,
+                })
+                .with_operation_id(operation_id);
+                self.metrics.record_result::<()>(&Err(error.clone()));
+                warn!(
+                    operation = L::OPERATION_KIND,
+                    operation_id,
+                    %error,
+                    "Coalesced FFI operation failed"
+                );
+                Err(error)
+            }
+        }
+    }
+
+    /// Dispatches all `operations` to FFI side according to `options` and
+    /// awaits their outcomes, in the same order as `operations`. Using the
+    /// batch executor of this dispatcher, if any, all operations cross the FFI
+    /// boundary at once, within the default timeout of this dispatcher and
+    /// before the deadline of `options`, without being retried or coalesced,
+    /// else every operation is dispatched on its own.
+    #[instrument(
+        name = "dispatch_batch",
+        skip_all,
+        fields(operation = L::OPERATION_KIND, count = operations.len())
+    )]
+    pub(crate) async fn dispatch_batch(
+        &self,
+        mut operations: Vec<L::Request>,
+        options: DispatchOptions,
+    ) -> Vec<Result<L::Response, FFIBridgeError>> {
+        let batch_executor = lock_unpoisoned(&self.state.batch_executor).clone();
+        let Some(batch_executor) = batch_executor.filter(|_| !operations.is_empty()) else {
+            return join_all(
+                operations
+                    .into_iter()
+                    .map(|operation| self.dispatch_with_options(operation, options.clone())),
+            )
+            .await;
+        };
+
+        // Identifies every operation of the batch, on both sides.
+        let operation_ids = operations
+            .iter_mut()
+            .map(|operation| {
+                let operation_id = next_operation_id();
+                operation.set_operation_id(operation_id);
+                operation_id
+            })
+            .collect::<Vec<_>>();
+
+        let timeout_deadline = self
+            .configuration
+            .timeout()
+            .map(|timeout| Instant::now() + timeout);
+        let deadline = earliest(options.deadline, timeout_deadline);
+        // The batch crosses the FFI boundary once, so counts as one operation.
+        let results = match self
+            .circuit_breaker
+            .try_acquire(L::OPERATION_KIND)
+            .map_err(FFIBridgeError::from)
+        {
+            Ok(circuit_permit) => {
+                let results = self
+                    .dispatch_batch_attempt(batch_executor, operations, deadline, options.priority)
+                    .await;
+                circuit_permit.record(&results);
+                results
+            }
+            Err(error) => Err(error),
+        };
+        let results = match results {
+            Ok(results) => results,
+            // The batch as a whole failed, and so did every operation of it.
+            Err(error) => vec![Err(error); operation_ids.len()],
+        };
+
+        results
+            .into_iter()
+            .zip(operation_ids)
+            .map(|(result, operation_id)| {
+                self.metrics.record_result(&result);
+                result.map_err(|error| error.with_operation_id(operation_id))
+            })
+            .collect()
+    }
+
+    async fn dispatch_batch_attempt(
+        &self,
+        batch_executor: Arc<dyn FFIOperationBatchExecutor<L>>,
+        operations: Vec<L::Request>,
+        deadline: Option<Instant>,
+        priority: OperationPriority,
+    ) -> Result<Vec<Result<L::Response, FFIBridgeError>>, FFIBridgeError> {
+        let started_at = Instant::now();
+        let timed_out = || RustSideError::OperationTimedOut {
+            operation: L::OPERATION_KIND.to_owned(),
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        };
+        let count = operations.len();
+
+        // The batch crosses the FFI boundary once, so takes a single permit.
+        let permit = self.concurrency_limiter.acquire(priority);
+        let _permit = match deadline {
+            Some(deadline) => timeout_at(deadline, permit)
+                .await
+                .map_err(|_| timed_out())?,
+            None => permit.await,
+        };
+
+        let interceptors = self.lock_interceptors().clone();
+        let operations = operations
+            .into_iter()
+            .map(|mut operation| {
+                operation.set_deadline(deadline.map(FFIOperationDeadline::new));
+                interceptors
+                    .iter()
+                    .fold(operation, |operation, interceptor| {
+                        interceptor.intercept_request(operation)
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let (sender, mut receiver) = channel::<Result<Vec<L::Outcome>, RustSideError>>();
+        let cancellation_handle = Arc::new(FFICancellationHandle::new());
+        let outcome_listener = FFIOperationOutcomeListener::new(
+            sender,
+            L::OPERATION_KIND,
+            next_operation_id(),
+            cancellation_handle.clone(),
+            None,
+        );
+
+        (0..count).for_each(|_| self.metrics.record_dispatched());
+        debug!("Dispatching batch of FFI operations");
+        let dispatched_at = Instant::now();
+        batch_executor
+            .execute_batch_request(
+                operations,
+                outcome_listener.into(),
+                cancellation_handle.clone(),
+            )
+            .map_err(FFIBridgeError::from)?;
+
+        let cancel_on_drop = cancellation_handle.cancel_on_drop();
+        let outcomes = match deadline {
+            Some(deadline) => timeout_at(deadline, &mut receiver)
+                .await
+                .map_err(|_| timed_out())?,
+            None => (&mut receiver).await,
+        }
+        .map_err(|_| RustSideError::FailedToReceiveResponseFromSwift)?
+        .inspect_err(report_if_host_bug)?;
+        cancel_on_drop.disarm();
+
+        if outcomes.len() != count {
+            return Err(RustSideError::BatchOutcomeCountMismatch {
+                operation: L::OPERATION_KIND.to_owned(),
+                expected: count as u64,
+                actual: outcomes.len() as u64,
+            }
+            .into());
+        }
+
+        let latency = dispatched_at.elapsed();
+        Ok(outcomes
+            .into_iter()
+            .map(|outcome| {
+                self.metrics.record_latency(latency);
+                for interceptor in interceptors.iter() {
+                    interceptor.intercept_outcome(&outcome);
+                }
+                outcome.into().map_err(|e| e.into().into())
+            })
+            .collect())
+    }
+
+    /// Dispatches `operation` to FFI side, retrying retryable failures
+    /// according to the [`RetryPolicy`] of this dispatcher, if any. Every
+    /// attempt, on every executor of the chain, must finish within `timeout`
+    /// and before the deadline of `options`.
+    #[instrument(
+        name = "dispatch",
+        skip_all,
+        fields(operation = L::OPERATION_KIND, operation_id = tracing::field::Empty)
+    )]
+    async fn dispatch_retrying(
+        &self,
+        mut operation: L::Request,
+        operation_id: u64,
+        timeout: Option<Duration>,
+        options: &DispatchOptions,
+    ) -> Result<Served<L::Response>, FFIBridgeError> {
+        operation.set_operation_id(operation_id);
+        tracing::Span::current().record("operation_id", operation_id);
+
+        let deadline = options.deadline;
+        let attempt_deadline =
+            || earliest(deadline, timeout.map(|timeout| Instant::now() + timeout));
+
+        let Some(retry_policy) = self.configuration.retry_policy.as_ref() else {
+            return self
+                .dispatch_once(operation, operation_id, attempt_deadline, options)
+                .await
+                .map_err(|error| error.with_operation_id(operation_id));
+        };
+
+        let mut attempt = 1;
+        loop {
+            let error = match self
+                .dispatch_once(operation.clone(), operation_id, attempt_deadline, options)
+                .await
+            {
+                Ok(response) => return Ok(response),
+                Err(error) => error.with_operation_id(operation_id),
+            };
+
+            if !error.is_retryable() {
+                return Err(error);
+            }
+            let Some(backoff) = retry_policy.backoff_after_attempt(attempt) else {
+                return Err(error);
+            };
+            if deadline.is_some_and(|deadline| Instant::now() + backoff >= deadline) {
+                return Err(error);
+            }
+
+            info!(
+                attempt,
+                backoff_ms = backoff.as_millis() as u64,
+                %error,
+                "Retrying FFI operation"
+            );
+            sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    /// Dispatches `operation` to FFI side once, unless the circuit is open, in
+    /// which case it fails fast, passing it on along the chain of executors
+    /// until an executor does not fail with a failure to fall back on.
+    /// Awaits the outcome from every executor until `attempt_deadline`, if
+    /// any, recording every result in the stats.
+    async fn dispatch_once(
+        &self,
+        operation: L::Request,
+        operation_id: u64,
+        attempt_deadline: impl Fn() -> Option<Instant>,
+        options: &DispatchOptions,
+    ) -> Result<Served<L::Response>, FFIBridgeError> {
+        let circuit_permit = match self.circuit_breaker.try_acquire(L::OPERATION_KIND) {
+            Ok(circuit_permit) => circuit_permit,
+            Err(error) => {
+                let error = FFIBridgeError::from(error);
+                self.metrics.record_result::<()>(&Err(error.clone()));
+                warn!(%error, "FFI operation failed");
+                return Err(error);
+            }
+        };
+
+        // Snapshot the chain, so the executor can be replaced meanwhile.
+        let executors = self.executors();
+        let mut executor_index = 0;
+        let result = loop {
+            let result = self
+                .dispatch_attempt(
+                    executors[executor_index].clone(),
+                    operation.clone(),
+                    operation_id,
+                    attempt_deadline(),
+                    options,
+                )
+                .await;
+            self.metrics.record_result(&result);
+            match result {
+                Ok(_) => debug!(executor_index, "FFI operation succeeded"),
+                Err(ref error) => warn!(executor_index, %error, "FFI operation failed"),
+            }
+            match result {
+                Err(error)
+                    if executor_index + 1 < executors.len()
+                        && self.configuration.falls_back_on(&error) =>
+                {
+                    executor_index += 1;
+                    info!(executor_index, "Falling back to next executor");
+                }
+                result => break result,
+            }
+        };
+        circuit_permit.record(&result);
+        result.map(|value| Served {
+            value,
+            executor_index: executor_index as u32,
+        })
+    }
+
+    async fn dispatch_attempt(
+        &self,
+        executor: Arc<dyn FFIOperationExecutor<L>>,
+        mut operation: L::Request,
+        operation_id: u64,
+        deadline: Option<Instant>,
+        options: &DispatchOptions,
+    ) -> Result<L::Response, FFIBridgeError> {
+        let started_at = Instant::now();
+        let timed_out = || RustSideError::OperationTimedOut {
+            operation: L::OPERATION_KIND.to_owned(),
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        };
+
+        // Wait for our turn if max operations are already in flight FFI side,
+        // which counts towards the timeout.
+        let permit = self.concurrency_limiter.acquire(options.priority);
+        let _permit = match deadline {
+            Some(deadline) => timeout_at(deadline, permit)
+                .await
+                .map_err(|_| timed_out())?,
+            None => permit.await,
+        };
+
+        // Let FFI side know when we give up, so it can set its own timeout.
+        operation.set_deadline(deadline.map(FFIOperationDeadline::new));
+
+        // Snapshot the chain, so interceptors are free to add interceptors.
+        let interceptors = self.lock_interceptors().clone();
+        let operation = interceptors
+            .iter()
+            .fold(operation, |operation, interceptor| {
+                interceptor.intercept_request(operation)
+            });
+
+        // Underlying channel used to get result from Swift back to Rust, or
+        // the error of the listener, if Swift drops it without notifying.
+        let (sender, mut receiver) = channel::<Result<L::Outcome, RustSideError>>();
+
+        // Lets Swift know if we no longer await the outcome
+        let cancellation_handle = Arc::new(FFICancellationHandle::new());
 
         // Our callback we pass to Swift
-        let outcome_listener = FFIOperationOutcomeListener::new(sender);
+        let outcome_listener = FFIOperationOutcomeListener::new(
+            sender,
+            L::OPERATION_KIND,
+            operation_id,
+            cancellation_handle.clone(),
+            options.progress_observer.clone(),
+        );
+        let outcome_claim = outcome_listener.outcome_claim();
 
         // Make request
-        self.executor
+        self.metrics.record_dispatched();
+        debug!("Dispatching FFI operation");
+        let dispatched_at = Instant::now();
+        let immediate_outcome = executor
             .execute_request(
                 // Pass operation to Swift to make
                 operation,
                 // Pass callback, Swift will call `result_listener.notify_outcome`
                 outcome_listener.into(),
+                // Swift can observe it to stop executing the operation
+                cancellation_handle.clone(),
             )
-            .map_err(|e| FFIBridgeError::from(e))?;
+            .map_err(FFIBridgeError::from)?;
+
+        let response = match immediate_outcome {
+            // Swift already knew the outcome, so there is nothing to await.
+            Some(outcome) => {
+                outcome_claim.claim();
+                if let Ok(Some(Ok(_))) = receiver.try_recv() {
+                    warn!("Outcome of FFI operation both notified and returned, using the returned one");
+                }
+                outcome
+            }
+            None => {
+                // If this future is dropped or times out before Swift has
+                // notified us about the outcome, we cancel the operation.
+                // Dropped before the `receiver`, so that a late
+                // `notify_outcome` is seen as cancelled.
+                let cancel_on_drop = cancellation_handle.cancel_on_drop();
 
-        // Await response from Swift
-        let response = receiver.await.map_err(|_| FFIBridgeError::FromRust {
-            error: RustSideError::FailedToReceiveResponseFromSwift,
-        })?;
+                // Await response from Swift
+                let response = match deadline {
+                    Some(deadline) => timeout_at(deadline, &mut receiver)
+                        .await
+                        .map_err(|_| timed_out())?,
+                    None => (&mut receiver).await,
+                }
+                .map_err(|_| RustSideError::FailedToReceiveResponseFromSwift)?
+                .inspect_err(report_if_host_bug)?;
+                cancel_on_drop.disarm();
+                response
+            }
+        };
+        self.metrics.record_latency(dispatched_at.elapsed());
+
+        for interceptor in interceptors.iter() {
+            interceptor.intercept_outcome(&response);
+        }
 
         response.into().map_err(|e| e.into().into())
     }
 }
+
+impl<L: IsStreamListener> FFIOperationDispatcher<L> {
+    /// Dispatches `operation` to FFI side and returns the [`Stream`] of the
+    /// values FFI side notifies Rust side about. Dropping the stream before it
+    /// has finished cancels the operation.
+    ///
+    /// At most `buffer_size` values of the state of this dispatcher are
+    /// buffered, from the moment the operation is dispatched, further values
+    /// are rejected with [`NotifyOutcomeError::StreamBufferFull`] until Rust
+    /// side has consumed some, so FFI side is never blocked.
+    ///
+    /// [`Stream`]: futures_core::Stream
+    pub(crate) fn dispatch_stream(
+        &self,
+        mut operation: L::Request,
+    ) -> Result<FFIOperationStream<L::Value>, FFIBridgeError> {
+        // Identifies the operation on both sides.
+        let operation_id = next_operation_id();
+        operation.set_operation_id(operation_id);
+
+        // Bounded channel used to get values from Swift back to Rust, the
+        // listener being its only sender, which has a slot of its own.
+        let (sender, receiver) = mpsc::channel(self.state.buffer_size.max(1) - 1);
+
+        // Lets Swift know if we no longer consume the stream
+        let cancellation_handle = Arc::new(FFICancellationHandle::new());
+        let stream = FFIOperationStream::new(
+            receiver,
+            L::OPERATION_KIND,
+            operation_id,
+            &cancellation_handle,
+            self.metrics.clone(),
+        );
+
+        // Our callback we pass to Swift
+        let stream_listener = FFIOperationStreamListener::new(
+            sender,
+            L::OPERATION_KIND,
+            operation_id,
+            cancellation_handle.clone(),
+        );
+
+        self.metrics.record_dispatched();
+        self.executor()
+            .execute_stream_request(operation, stream_listener.into(), cancellation_handle)
+            .map_err(|error| FFIBridgeError::from(error).with_operation_id(operation_id))
+            .inspect_err(|error| self.metrics.record_result::<()>(&Err(error.clone())))?;
+        debug!(
+            operation = L::OPERATION_KIND,
+            operation_id, "Dispatched FFI stream operation"
+        );
+
+        Ok(stream)
+    }
+}
+
+/// The earlier of two deadlines, `None` meaning no deadline at all.
+fn earliest(deadline: Option<Instant>, other: Option<Instant>) -> Option<Instant> {
+    match (deadline, other) {
+        (Some(deadline), Some(other)) => Some(deadline.min(other)),
+        (deadline, other) => deadline.or(other),
+    }
+}
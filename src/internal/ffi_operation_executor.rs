@@ -1,9 +1,19 @@
 use crate::prelude::*;
 
 pub trait FFIOperationExecutor<L: IsOutcomeListener>: Send + Sync {
+    /// Executes `request`, notifying its outcome using `listener_rust_side`,
+    /// or returning it if already known, e.g. a cache hit, in which case the
+    /// returned outcome wins over any notified one.
     fn execute_request(
         &self,
         request: L::Request,
         listener_rust_side: L,
-    ) -> Result<(), FFISideError>;
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<L::Outcome>, FFISideError>;
+
+    /// What this executor supports, if known, by default unknown, in which
+    /// case Rust side assumes the least.
+    fn capabilities(&self) -> Option<L::Capabilities> {
+        None
+    }
 }
@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+/// A cross-cutting concern of the operations of a [`FFIOperationDispatcher`],
+/// e.g. header injection, logging or metrics, which can inspect and modify
+/// every request before it is passed to FFI side (Swift side), and inspect
+/// every outcome FFI side notifies Rust side about.
+///
+/// Interceptors run in the order they were added to the dispatcher, for every
+/// attempt of an operation.
+pub trait FFIOperationInterceptor<L: IsOutcomeListener>: Send + Sync {
+    /// Called with the request right before it is passed to the executor,
+    /// returns the request to pass on, by default unchanged.
+    fn intercept_request(&self, request: L::Request) -> L::Request {
+        request
+    }
+
+    /// Called with the outcome FFI side notified Rust side about, before it is
+    /// returned to the caller. Not called if the operation was never notified
+    /// about, e.g. due to timeout.
+    fn intercept_outcome(&self, _outcome: &L::Outcome) {}
+}
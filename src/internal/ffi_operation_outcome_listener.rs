@@ -4,28 +4,205 @@ use crate::prelude::*;
 /// carried out by FFI (Swift-side). When FFI side has finished the operation,
 /// either successfully or with failure, it passes back this result to Rust
 /// side by calling `notify_outcome`. This is effectively a callback pattern.
+///
+/// If FFI side drops the listener without notifying the outcome, the
+/// dispatcher awaiting it fails with [`RustSideError::ListenerDroppedWithoutOutcome`].
 pub struct FFIOperationOutcomeListener<R> {
-    sender: Mutex<Option<Sender<R>>>,
+    /// Shared with the [`OutcomeClaim`] of the dispatcher.
+    sender: Arc<Mutex<Option<OutcomeSender<R>>>>,
+    operation: &'static str,
+    operation_id: u64,
+    created_at: Instant,
+    cancellation_handle: Arc<FFICancellationHandle>,
+    progress_observer: Option<Arc<dyn FFIOperationProgressObserver>>,
+}
+
+/// Where a [`FFIOperationOutcomeListener`] passes the outcome on to.
+enum OutcomeSender<R> {
+    /// The dispatcher awaiting the outcome.
+    Channel(Sender<Result<R, RustSideError>>),
+
+    /// A Rust side executor wrapping another executor, e.g. to record the
+    /// outcome before notifying its own listener about it.
+    Forward(Box<dyn ForwardOutcome<R>>),
+}
+
+/// Passes the outcome of a forwarding listener on, or the error Rust side
+/// failed the operation with.
+trait ForwardOutcome<R>:
+    FnOnce(Result<R, RustSideError>) -> Result<(), NotifyOutcomeError> + Send
+{
+}
+
+impl<R, F> ForwardOutcome<R> for F where
+    F: FnOnce(Result<R, RustSideError>) -> Result<(), NotifyOutcomeError> + Send
+{
 }
 
 impl<R> FFIOperationOutcomeListener<R> {
-    pub(crate) fn new(sender: Sender<R>) -> Self {
+    pub(crate) fn new(
+        sender: Sender<Result<R, RustSideError>>,
+        operation: &'static str,
+        operation_id: u64,
+        cancellation_handle: Arc<FFICancellationHandle>,
+        progress_observer: Option<Arc<dyn FFIOperationProgressObserver>>,
+    ) -> Self {
         Self {
-            sender: Mutex::new(Some(sender)),
+            sender: Arc::new(Mutex::new(Some(OutcomeSender::Channel(sender)))),
+            operation,
+            operation_id,
+            created_at: Instant::now(),
+            cancellation_handle,
+            progress_observer,
+        }
+    }
+
+    /// A listener passing the outcome on to `forward`, for Rust side executors
+    /// wrapping another executor. Progress is not passed on.
+    pub(crate) fn forwarding(
+        operation: &'static str,
+        operation_id: u64,
+        cancellation_handle: Arc<FFICancellationHandle>,
+        forward: impl FnOnce(Result<R, RustSideError>) -> Result<(), NotifyOutcomeError>
+            + Send
+            + 'static,
+    ) -> Self {
+        Self {
+            sender: Arc::new(Mutex::new(Some(OutcomeSender::Forward(Box::new(forward))))),
+            operation,
+            operation_id,
+            created_at: Instant::now(),
+            cancellation_handle,
+            progress_observer: None,
+        }
+    }
+
+    /// The ID Rust side generated for the dispatch of the operation.
+    pub(crate) fn operation_id(&self) -> u64 {
+        self.operation_id
+    }
+
+    /// Lets the dispatcher claim the outcome of the operation, if the executor
+    /// returns it from `execute_request` instead of notifying it.
+    pub(crate) fn outcome_claim(&self) -> OutcomeClaim<R> {
+        OutcomeClaim {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// This is called from FFI Side (Swift side) any number of times before
+    /// `notify_outcome`, when the operation has made progress, e.g. written
+    /// `completed` bytes out of `total`.
+    ///
+    /// Progress is best effort, progress notified after the outcome, or after
+    /// Rust side has cancelled the operation, is ignored.
+    pub(crate) fn notify_progress(&self, completed: u64, total: Option<u64>) {
+        let Some(progress_observer) = self.progress_observer.as_ref() else {
+            return;
+        };
+        let is_awaited = lock_unpoisoned(&self.sender).is_some();
+        if !is_awaited || self.cancellation_handle.is_cancelled() {
+            return;
         }
+        progress_observer.on_progress(FFIOperationProgress { completed, total })
     }
 
     /// This is called from FFI Side (Swift side), inside the implementation of
     /// an `execute_request:operation:listener_rust_side` method on a [`FFIOperationExecutor`],
     /// when the operation has finished, with the `result` of type Self::R
-    pub(crate) fn notify_outcome(&self, result: R) {
-        self.sender
-            .lock()
-            .expect("Should only have access sender Mutex once.")
-            .take()
-            .expect("You MUST NOT call `notifyOutcome` twice in Swift.")
-            .send(result)
-            .map_err(|_| RustSideError::FailedToPropagateResultFromFFIOperationBackToDispatcher)
-            .expect("Must never fail, since some context's in FFI side cannot be throwing.")
+    ///
+    /// Never panics, since that would abort FFI side. Notifying the outcome of
+    /// an operation Rust side has cancelled, e.g. due to timeout, is a no-op.
+    pub(crate) fn notify_outcome(&self, result: R) -> Result<(), NotifyOutcomeError> {
+        self.send(Ok(result))
+    }
+
+    /// Fails the operation with `error`, instead of notifying its outcome,
+    /// for Rust side executors failing Rust side, e.g. a cassette without a
+    /// recorded outcome for the request.
+    pub(crate) fn notify_rust_side_error(
+        &self,
+        error: RustSideError,
+    ) -> Result<(), NotifyOutcomeError> {
+        self.send(Err(error))
+    }
+
+    fn send(&self, result: Result<R, RustSideError>) -> Result<(), NotifyOutcomeError> {
+        // The lock is never held while panicking, so the sender is intact
+        // even if the mutex is poisoned.
+        let sender = lock_unpoisoned(&self.sender).take();
+
+        let Some(sender) = sender else {
+            warn!(
+                operation = self.operation,
+                operation_id = self.operation_id,
+                "Outcome of FFI operation notified more than once"
+            );
+            return Err(NotifyOutcomeError::AlreadyNotified {
+                operation: self.operation.to_owned(),
+                operation_id: self.operation_id,
+            });
+        };
+
+        let sender = match sender {
+            OutcomeSender::Channel(sender) => sender,
+            OutcomeSender::Forward(forward) => return forward(result),
+        };
+
+        if sender.send(result).is_ok() {
+            return Ok(());
+        }
+
+        if self.cancellation_handle.is_cancelled() {
+            debug!(
+                operation = self.operation,
+                operation_id = self.operation_id,
+                "Ignored outcome of FFI operation notified after it was cancelled"
+            );
+            Ok(())
+        } else {
+            warn!(
+                operation = self.operation,
+                operation_id = self.operation_id,
+                "Outcome of FFI operation notified after Rust side stopped awaiting it"
+            );
+            Err(NotifyOutcomeError::NoLongerAwaited {
+                operation: self.operation.to_owned(),
+                operation_id: self.operation_id,
+            })
+        }
+    }
+}
+
+impl<R> Drop for FFIOperationOutcomeListener<R> {
+    /// Fails the dispatcher awaiting the outcome, if FFI side never notified
+    /// it, e.g. if the executor lost track of the operation.
+    fn drop(&mut self) {
+        let sender = lock_unpoisoned(&self.sender).take();
+        // The listener a forwarding listener forwards to reports itself.
+        let Some(OutcomeSender::Channel(sender)) = sender else {
+            return;
+        };
+        // Fails only if Rust side no longer awaits the outcome, e.g. since it
+        // cancelled the operation, so FFI side rightly stopped executing it.
+        let _ = sender.send(Err(RustSideError::ListenerDroppedWithoutOutcome {
+            operation: self.operation.to_owned(),
+            operation_id: self.operation_id,
+            elapsed_ms: self.created_at.elapsed().as_millis() as u64,
+        }));
+    }
+}
+
+/// Claims the outcome of an operation the executor returned from
+/// `execute_request`, which wins over any outcome notified using the listener,
+/// so that notifying one afterwards fails with
+/// [`NotifyOutcomeError::AlreadyNotified`].
+pub(crate) struct OutcomeClaim<R> {
+    sender: Arc<Mutex<Option<OutcomeSender<R>>>>,
+}
+
+impl<R> OutcomeClaim<R> {
+    pub(crate) fn claim(self) {
+        lock_unpoisoned(&self.sender).take();
     }
 }
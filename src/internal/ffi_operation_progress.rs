@@ -0,0 +1,21 @@
+use crate::prelude::*;
+
+/// The progress of an operation carried out by FFI side (Swift side), e.g. the
+/// number of bytes written of a file, or downloaded of a response body.
+#[derive(Record, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FFIOperationProgress {
+    /// The amount of work completed so far, e.g. number of bytes.
+    pub completed: u64,
+
+    /// The total amount of work, in the same unit as `completed`, if known.
+    pub total: Option<u64>,
+}
+
+/// Observes the progress of an operation carried out by FFI side (Swift side),
+/// implemented either Rust side or FFI side, e.g. to drive a progress bar.
+#[uniffi::export(with_foreign)]
+pub trait FFIOperationProgressObserver: Send + Sync {
+    /// Called every time FFI side notifies Rust side about progress of the
+    /// operation, on the thread FFI side did so.
+    fn on_progress(&self, progress: FFIOperationProgress);
+}
@@ -0,0 +1,116 @@
+use crate::prelude::*;
+use futures_channel::mpsc::Receiver;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The number of values FFI side (Swift side) can notify before Rust side has
+/// consumed them, after which `notify_value` fails with
+/// [`NotifyOutcomeError::StreamBufferFull`].
+const DEFAULT_STREAM_BUFFER_SIZE: usize = 16;
+
+/// What a dispatcher of stream operations keeps apart from its executors.
+pub struct StreamDispatcherState {
+    /// The number of values FFI side can notify before Rust side has consumed
+    /// them, from the moment the operation is dispatched.
+    pub buffer_size: usize,
+}
+
+impl Default for StreamDispatcherState {
+    fn default() -> Self {
+        Self {
+            buffer_size: DEFAULT_STREAM_BUFFER_SIZE,
+        }
+    }
+}
+
+/// The values of a stream operation FFI side (Swift side) notifies Rust side
+/// about, ending after the first error.
+pub struct FFIOperationStream<T> {
+    receiver: Receiver<FFIOperationStreamEvent<T>>,
+    operation: &'static str,
+    operation_id: u64,
+    cancel_on_drop: Option<CancelOnDrop>,
+    /// The metrics of the dispatcher, recording how the stream finished.
+    metrics: Arc<DispatcherMetrics>,
+}
+
+impl<T> FFIOperationStream<T> {
+    pub(crate) fn new(
+        receiver: Receiver<FFIOperationStreamEvent<T>>,
+        operation: &'static str,
+        operation_id: u64,
+        cancellation_handle: &Arc<FFICancellationHandle>,
+        metrics: Arc<DispatcherMetrics>,
+    ) -> Self {
+        Self {
+            receiver,
+            operation,
+            operation_id,
+            cancel_on_drop: Some(cancellation_handle.cancel_on_drop()),
+            metrics,
+        }
+    }
+
+    /// Awaits the next value of this stream, `None` once it has finished.
+    pub(crate) async fn next(&mut self) -> Option<Result<T, FFIBridgeError>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+
+    fn finish(&mut self) {
+        // Anything FFI side notifies after this is rejected.
+        self.receiver.close();
+        if let Some(cancel_on_drop) = self.cancel_on_drop.take() {
+            cancel_on_drop.disarm();
+        }
+    }
+}
+
+impl<T> Stream for FFIOperationStream<T> {
+    type Item = Result<T, FFIBridgeError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.cancel_on_drop.is_none() {
+            return Poll::Ready(None);
+        }
+        let item = match Pin::new(&mut self.receiver).poll_next(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Some(FFIOperationStreamEvent::Value(value))) => {
+                return Poll::Ready(Some(Ok(value)))
+            }
+            Poll::Ready(Some(FFIOperationStreamEvent::Failed(error))) => {
+                Some(Err(error.with_operation_id(self.operation_id)))
+            }
+            Poll::Ready(Some(FFIOperationStreamEvent::Completed)) => None,
+            // FFI side dropped the listener without finishing the stream.
+            Poll::Ready(None) => Some(Err(FFIBridgeError::from(
+                RustSideError::FailedToReceiveResponseFromSwift,
+            )
+            .with_operation_id(self.operation_id))),
+        };
+        match &item {
+            Some(Err(error)) => {
+                warn!(
+                    operation = self.operation,
+                    operation_id = self.operation_id,
+                    %error,
+                    "Stream of FFI operation failed"
+                );
+                if let FFIBridgeError::FromRust { error, .. } = error {
+                    report_if_host_bug(error);
+                }
+                self.metrics.record_result::<()>(&Err(error.clone()));
+            }
+            _ => {
+                debug!(
+                    operation = self.operation,
+                    operation_id = self.operation_id,
+                    "Stream of FFI operation completed"
+                );
+                self.metrics.record_result(&Ok(()));
+            }
+        }
+        self.finish();
+        Poll::Ready(item)
+    }
+}
@@ -0,0 +1,10 @@
+use crate::prelude::*;
+
+pub trait FFIOperationStreamExecutor<L: IsStreamListener>: Send + Sync {
+    fn execute_stream_request(
+        &self,
+        request: L::Request,
+        listener_rust_side: L,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<(), FFISideError>;
+}
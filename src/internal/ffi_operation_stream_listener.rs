@@ -0,0 +1,182 @@
+use crate::prelude::*;
+use futures_channel::mpsc;
+use std::task::Poll;
+
+/// An event FFI side (Swift side) passes to Rust side about an ongoing stream
+/// operation, using a [`FFIOperationStreamListener`].
+pub(crate) enum FFIOperationStreamEvent<T> {
+    Value(T),
+    Failed(FFIBridgeError),
+    Completed,
+}
+
+/// An object representing that Rust is listening on the values of a stream
+/// operation carried out by FFI (Swift-side), e.g. the chunks of a download.
+/// FFI side passes back any number of values using `notify_value` and then
+/// finishes the stream by calling either `notify_completed` or `notify_failed`.
+///
+/// Rust side buffers a bounded number of values, and `notify_value` never
+/// blocks: it fails with [`NotifyOutcomeError::StreamBufferFull`] while the
+/// buffer is full, whereas `notify_value_async` awaits room for the value.
+/// Finishing the stream always succeeds, however full the buffer is.
+///
+/// If FFI side drops the listener without finishing the stream, the stream
+/// fails with [`RustSideError::ListenerDroppedWithoutOutcome`].
+pub struct FFIOperationStreamListener<T> {
+    /// `None` once the stream has finished, which ends the stream Rust side
+    /// after the events already sent.
+    sender: Mutex<Option<mpsc::Sender<FFIOperationStreamEvent<T>>>>,
+    operation: &'static str,
+    operation_id: u64,
+    created_at: Instant,
+    cancellation_handle: Arc<FFICancellationHandle>,
+}
+
+impl<T> FFIOperationStreamListener<T> {
+    pub(crate) fn new(
+        sender: mpsc::Sender<FFIOperationStreamEvent<T>>,
+        operation: &'static str,
+        operation_id: u64,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Self {
+        Self {
+            sender: Mutex::new(Some(sender)),
+            operation,
+            operation_id,
+            created_at: Instant::now(),
+            cancellation_handle,
+        }
+    }
+
+    /// The ID Rust side generated for the dispatch of the operation.
+    pub(crate) fn operation_id(&self) -> u64 {
+        self.operation_id
+    }
+
+    fn lock_sender(&self) -> MutexGuard<'_, Option<mpsc::Sender<FFIOperationStreamEvent<T>>>> {
+        lock_unpoisoned(&self.sender)
+    }
+
+    /// This is called from FFI Side (Swift side) for every value of the
+    /// stream, failing with [`NotifyOutcomeError::StreamBufferFull`] instead
+    /// of blocking if Rust side has no room for it.
+    pub(crate) fn notify_value(&self, value: T) -> Result<(), NotifyOutcomeError> {
+        let mut sender = self.lock_sender();
+        let Some(sender) = sender.as_mut() else {
+            return Err(self.already_finished());
+        };
+        match sender.try_send(FFIOperationStreamEvent::Value(value)) {
+            Ok(()) => Ok(()),
+            Err(error) if error.is_full() => Err(NotifyOutcomeError::StreamBufferFull {
+                operation: self.operation.to_owned(),
+                operation_id: self.operation_id,
+            }),
+            Err(_) => self.no_longer_awaited(),
+        }
+    }
+
+    /// This is called from FFI Side (Swift side) for every value of the
+    /// stream, awaiting room for it if Rust side has none.
+    pub(crate) async fn notify_value_async(&self, value: T) -> Result<(), NotifyOutcomeError> {
+        let mut event = Some(FFIOperationStreamEvent::Value(value));
+        // The lock is only held while polling, never across awaiting.
+        let is_sent = std::future::poll_fn(|cx| {
+            let mut sender = self.lock_sender();
+            let Some(sender) = sender.as_mut() else {
+                return Poll::Ready(None);
+            };
+            sender.poll_ready(cx).map(|ready| {
+                Some(
+                    ready.is_ok()
+                        && event
+                            .take()
+                            .is_some_and(|event| sender.start_send(event).is_ok()),
+                )
+            })
+        })
+        .await;
+        match is_sent {
+            None => Err(self.already_finished()),
+            Some(false) => self.no_longer_awaited(),
+            Some(true) => Ok(()),
+        }
+    }
+
+    /// This is called from FFI Side (Swift side) when the stream has finished
+    /// successfully, no more values can be notified after this.
+    pub(crate) fn notify_completed(&self) -> Result<(), NotifyOutcomeError> {
+        self.finish(FFIOperationStreamEvent::Completed)
+    }
+
+    /// This is called from FFI Side (Swift side) when the stream has finished
+    /// with `error`, no more values can be notified after this.
+    pub(crate) fn notify_failed(
+        &self,
+        error: impl Into<FFISideError>,
+    ) -> Result<(), NotifyOutcomeError> {
+        let error = FFIBridgeError::from(error.into());
+        self.finish(FFIOperationStreamEvent::Failed(error))
+    }
+
+    /// Sends the final `event` of the stream, even if the buffer is full,
+    /// since every clone of a sender has a slot of its own.
+    fn finish(&self, event: FFIOperationStreamEvent<T>) -> Result<(), NotifyOutcomeError> {
+        let Some(sender) = self.lock_sender().take() else {
+            return Err(self.already_finished());
+        };
+        if sender.clone().try_send(event).is_ok() {
+            return Ok(());
+        }
+        self.no_longer_awaited()
+    }
+
+    /// Never panics, since that would abort FFI side.
+    fn already_finished(&self) -> NotifyOutcomeError {
+        warn!(
+            operation = self.operation,
+            operation_id = self.operation_id,
+            "Stream of FFI operation notified after it finished"
+        );
+        NotifyOutcomeError::StreamAlreadyFinished {
+            operation: self.operation.to_owned(),
+            operation_id: self.operation_id,
+        }
+    }
+
+    /// Notifying an event of a stream Rust side has cancelled, e.g. by
+    /// dropping it, is a no-op.
+    fn no_longer_awaited(&self) -> Result<(), NotifyOutcomeError> {
+        if self.cancellation_handle.is_cancelled() {
+            return Ok(());
+        }
+        warn!(
+            operation = self.operation,
+            operation_id = self.operation_id,
+            "Stream of FFI operation notified after Rust side stopped awaiting it"
+        );
+        Err(NotifyOutcomeError::NoLongerAwaited {
+            operation: self.operation.to_owned(),
+            operation_id: self.operation_id,
+        })
+    }
+}
+
+impl<T> Drop for FFIOperationStreamListener<T> {
+    /// Fails the stream, if FFI side never finished it, e.g. if the executor
+    /// lost track of the operation, unless Rust side no longer consumes it.
+    fn drop(&mut self) {
+        let Some(sender) = self.lock_sender().take() else {
+            return;
+        };
+        if !self.cancellation_handle.is_cancelled() {
+            let error = RustSideError::ListenerDroppedWithoutOutcome {
+                operation: self.operation.to_owned(),
+                operation_id: self.operation_id,
+                elapsed_ms: self.created_at.elapsed().as_millis() as u64,
+            };
+            let _ = sender
+                .clone()
+                .try_send(FFIOperationStreamEvent::Failed(error.into()));
+        }
+    }
+}
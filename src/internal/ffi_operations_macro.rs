@@ -0,0 +1,499 @@
+/// Declares all FFI operations Rust side can dispatch to FFI side (Swift side),
+/// each operation declared once using its `name`, `kind`, and the types of its
+/// `request`, `response`, `failure` and `capabilities`, e.g.:
+///
+/// ```ignore
+/// ffi_operations! {
+///     Networking {
+///         kind: networking,
+///         request: FFINetworkingRequest,
+///         response: FFINetworkingResponse,
+///         failure: FFINetworkingError,
+///         capabilities: FFINetworkingCapabilities,
+///     },
+/// }
+/// ```
+///
+/// Which generates:
+/// * `FFINetworkingOutcome` - `Success { value }` / `Failure { error }` enum,
+/// convertible into `Result<FFINetworkingResponse, FFINetworkingError>`.
+/// * `FFINetworkingOutcomeListener` - the [`IsOutcomeListener`] object on which
+/// FFI side calls `notify_outcome`.
+/// * `FFINetworkingExecutor` - the trait FFI side implements, with the methods
+/// `execute_networking_request` and `networking_capabilities`, advertising
+/// what the executor supports, if known, which is also an [`FFIOperationExecutor`].
+/// * `FFINetworkingBatchExecutor` - a trait FFI side can implement to carry
+/// out many requests in one FFI crossing, with the method
+/// `execute_networking_batch_request`, notifying `FFINetworkingBatchOutcomeListener`
+/// about all outcomes at once, which is also an [`FFIOperationBatchExecutor`].
+/// * `FFINetworkingInterceptor` - a trait FFI side can implement to intercept
+/// requests and outcomes, which is also an [`FFIOperationInterceptor`].
+/// * An [`IsOperationRequest`] impl for `FFINetworkingRequest`, which must
+/// have a `deadline: Option<FFIOperationDeadline>` and an
+/// `operation_id: Option<u64>` field.
+/// * The `FFISideError::Networking` variant, wrapping `FFINetworkingError`,
+/// which must implement [`IsRetryable`].
+///
+/// An operation producing any number of values, instead of a single response,
+/// is declared using `stream` instead of `response`, e.g.
+/// `stream: Vec<u8>`, generating a `FFI{Name}StreamListener` on which FFI side
+/// calls `notify_value`, or `notify_value_async`, and then `notify_completed`
+/// or `notify_failed`, without `capabilities`, an executor
+/// which is a [`FFIOperationStreamExecutor`], dispatched using
+/// `dispatch_stream` of a [`FFIOperationDispatcher`], and an [`IsOperationRequest`] impl for the
+/// request, which must have an `operation_id: Option<u64>` field.
+///
+/// Doc comments of an operation are put on its executor trait.
+macro_rules! ffi_operations {
+    ($(
+        $(#[$executor_meta:meta])*
+        $name:ident {
+            kind: $kind:ident,
+            request: $request:ident,
+            $shape:ident: $output:ty,
+            failure: $failure:ident
+            $(, capabilities: $capabilities:ident)? $(,)?
+        }
+    ),+ $(,)?) => {
+        #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error, uniffi::Error)]
+        pub enum FFISideError {
+            $(
+                #[error(transparent)]
+                $name {
+                    #[from]
+                    error: $failure,
+                },
+            )+
+        }
+
+        impl $crate::prelude::IsRetryable for FFISideError {
+            fn is_retryable(&self) -> bool {
+                match self {
+                    $(
+                        Self::$name { error } => error.is_retryable(),
+                    )+
+                }
+            }
+        }
+
+        $(
+            $crate::prelude::ffi_operation! {
+                $shape
+                $(#[$executor_meta])*
+                $name {
+                    kind: $kind,
+                    request: $request,
+                    output: $output,
+                    failure: $failure,
+                    $(capabilities: $capabilities,)?
+                }
+            }
+        )+
+    };
+}
+
+/// Generates the glue of a single operation declared in [`ffi_operations`],
+/// depending on if it produces a single `response` or a `stream` of values.
+macro_rules! ffi_operation {
+    (
+        response
+        $(#[$executor_meta:meta])*
+        $name:ident {
+            kind: $kind:ident,
+            request: $request:ident,
+            output: $response:ty,
+            failure: $failure:ident,
+            capabilities: $capabilities:ident,
+        }
+    ) => {
+        paste::paste! {
+            #[doc = "The outcome of a `" $name "` operation FFI side (Swift side) passes back to Rust side."]
+            #[derive(uniffi::Enum, Clone, Debug)]
+            pub enum [<FFI $name Outcome>] {
+                Success { value: $response },
+                Failure { error: $failure },
+            }
+
+            impl From<[<FFI $name Outcome>]> for Result<$response, $failure> {
+                fn from(value: [<FFI $name Outcome>]) -> Self {
+                    match value {
+                        [<FFI $name Outcome>]::Success { value } => Ok(value),
+                        [<FFI $name Outcome>]::Failure { error } => Err(error),
+                    }
+                }
+            }
+
+            #[doc = "The listener FFI side (Swift side) notifies about the outcome of a `" $name "` operation."]
+            #[derive(uniffi::Object)]
+            pub struct [<FFI $name OutcomeListener>] {
+                result_listener: $crate::prelude::FFIOperationOutcomeListener<[<FFI $name Outcome>]>,
+            }
+
+            impl $crate::prelude::IsListener for [<FFI $name OutcomeListener>] {
+                type Executor = dyn $crate::prelude::FFIOperationExecutor<Self>;
+                type DispatcherState = $crate::prelude::OutcomeDispatcherState<Self>;
+            }
+
+            impl $crate::prelude::IsOutcomeListener for [<FFI $name OutcomeListener>] {
+                const OPERATION_KIND: &'static str = stringify!($kind);
+
+                type Request = $request;
+                type Response = $response;
+                type Failure = $failure;
+                type Outcome = [<FFI $name Outcome>];
+                type BatchListener = [<FFI $name BatchOutcomeListener>];
+                type Capabilities = $capabilities;
+            }
+
+            impl From<$crate::prelude::FFIOperationOutcomeListener<[<FFI $name Outcome>]>>
+                for [<FFI $name OutcomeListener>]
+            {
+                fn from(
+                    value: $crate::prelude::FFIOperationOutcomeListener<[<FFI $name Outcome>]>,
+                ) -> Self {
+                    Self::with_result_listener(value)
+                }
+            }
+
+            impl [<FFI $name OutcomeListener>] {
+                pub fn with_result_listener(
+                    result_listener: $crate::prelude::FFIOperationOutcomeListener<
+                        [<FFI $name Outcome>],
+                    >,
+                ) -> Self {
+                    Self { result_listener }
+                }
+
+                /// Fails the operation with `error` Rust side, instead of notifying its
+                /// outcome, for executors implemented Rust side.
+                pub fn notify_rust_side_error(
+                    &self,
+                    error: $crate::prelude::RustSideError,
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.result_listener.notify_rust_side_error(error)
+                }
+            }
+
+            #[uniffi::export]
+            impl [<FFI $name OutcomeListener>] {
+                /// The ID Rust side generated for the dispatch of the operation, the same
+                /// as the `operation_id` of the request.
+                pub fn operation_id(&self) -> u64 {
+                    self.result_listener.operation_id()
+                }
+
+                /// This is called from FFI Side (Swift side), inside the implementation of
+                #[doc = "an `execute_" $kind "_request` method on a [`FFI" $name "Executor`],"]
+                #[doc = "when the operation has finished, with the [`FFI" $name "Outcome`]."]
+                pub fn notify_outcome(
+                    &self,
+                    result: [<FFI $name Outcome>],
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.result_listener.notify_outcome(result)
+                }
+
+                /// This is called from FFI Side (Swift side) any number of times before
+                /// `notify_outcome`, when the operation has made progress, e.g. written
+                /// `completed` bytes out of `total`, if known.
+                pub fn notify_progress(&self, completed: u64, total: Option<u64>) {
+                    self.result_listener.notify_progress(completed, total)
+                }
+            }
+
+            $(#[$executor_meta])*
+            #[uniffi::export(with_foreign)]
+            pub trait [<FFI $name Executor>]:
+                $crate::prelude::FFIOperationExecutor<[<FFI $name OutcomeListener>]>
+            {
+                /// Rust will tell the handler to execute `request` by calling this
+                /// function, which a concrete type FFI side (Swift side) has implemented.
+                /// Once the operation has finished with a result (Success/Failure) it
+                /// passes back the result using the `listener_rust_side` callback. If Rust
+                /// side no longer awaits the result it cancels the `cancellation_handle`.
+                ///
+                /// If the outcome is already known, e.g. a cache hit, it can instead be
+                /// returned, sparing the callback. A returned outcome wins over any outcome
+                /// notified using the callback, notifying after returning one fails. Returns
+                /// `None` if the outcome will be notified using the callback.
+                fn [<execute_ $kind _request>](
+                    &self,
+                    request: $request,
+                    listener_rust_side: std::sync::Arc<[<FFI $name OutcomeListener>]>,
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<Option<[<FFI $name Outcome>]>, FFISideError>;
+
+                /// What this executor supports, so that Rust side can adapt to it, or
+                /// `None` if unknown, in which case Rust side assumes the least.
+                fn [<$kind _capabilities>](&self) -> Option<$capabilities>;
+            }
+
+            impl<U: [<FFI $name Executor>]>
+                $crate::prelude::FFIOperationExecutor<[<FFI $name OutcomeListener>]> for U
+            {
+                fn execute_request(
+                    &self,
+                    request: $request,
+                    listener_rust_side: [<FFI $name OutcomeListener>],
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<Option<[<FFI $name Outcome>]>, FFISideError> {
+                    self.[<execute_ $kind _request>](
+                        request,
+                        listener_rust_side.into(),
+                        cancellation_handle,
+                    )
+                }
+
+                fn capabilities(&self) -> Option<$capabilities> {
+                    self.[<$kind _capabilities>]()
+                }
+            }
+
+            #[doc = "The listener FFI side (Swift side) notifies about the outcomes of a batch of `" $name "` operations."]
+            #[derive(uniffi::Object)]
+            pub struct [<FFI $name BatchOutcomeListener>] {
+                result_listener: $crate::prelude::FFIOperationOutcomeListener<Vec<[<FFI $name Outcome>]>>,
+            }
+
+            impl From<$crate::prelude::FFIOperationOutcomeListener<Vec<[<FFI $name Outcome>]>>>
+                for [<FFI $name BatchOutcomeListener>]
+            {
+                fn from(
+                    value: $crate::prelude::FFIOperationOutcomeListener<Vec<[<FFI $name Outcome>]>>,
+                ) -> Self {
+                    Self {
+                        result_listener: value,
+                    }
+                }
+            }
+
+            #[uniffi::export]
+            impl [<FFI $name BatchOutcomeListener>] {
+                /// The ID Rust side generated for the dispatch of the batch, distinct from
+                /// the `operation_id` of each of its requests.
+                pub fn operation_id(&self) -> u64 {
+                    self.result_listener.operation_id()
+                }
+
+                /// This is called from FFI Side (Swift side), inside the implementation of
+                #[doc = "an `execute_" $kind "_batch_request` method on a [`FFI" $name "BatchExecutor`],"]
+                /// when all operations of the batch have finished, with one outcome per
+                /// request, in the same order as the requests.
+                pub fn notify_outcomes(
+                    &self,
+                    outcomes: Vec<[<FFI $name Outcome>]>,
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.result_listener.notify_outcome(outcomes)
+                }
+            }
+
+            #[doc = "An executor of batches of `" $name "` operations implemented FFI side (Swift side),"]
+            /// carrying out many requests in a single FFI crossing.
+            #[uniffi::export(with_foreign)]
+            pub trait [<FFI $name BatchExecutor>]:
+                $crate::prelude::FFIOperationBatchExecutor<[<FFI $name OutcomeListener>]>
+            {
+                /// Rust will tell the handler to execute all `requests` by calling this
+                /// function, passing back all their outcomes at once using the
+                /// `listener_rust_side` callback. If Rust side no longer awaits the
+                /// outcomes it cancels the `cancellation_handle`.
+                fn [<execute_ $kind _batch_request>](
+                    &self,
+                    requests: Vec<$request>,
+                    listener_rust_side: std::sync::Arc<[<FFI $name BatchOutcomeListener>]>,
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<(), FFISideError>;
+            }
+
+            impl<U: [<FFI $name BatchExecutor>]>
+                $crate::prelude::FFIOperationBatchExecutor<[<FFI $name OutcomeListener>]> for U
+            {
+                fn execute_batch_request(
+                    &self,
+                    requests: Vec<$request>,
+                    listener_rust_side: [<FFI $name BatchOutcomeListener>],
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<(), FFISideError> {
+                    self.[<execute_ $kind _batch_request>](
+                        requests,
+                        listener_rust_side.into(),
+                        cancellation_handle,
+                    )
+                }
+            }
+
+            #[doc = "An interceptor of `" $name "` operations implemented FFI side (Swift side),"]
+            /// e.g. for header injection, logging or metrics.
+            #[uniffi::export(with_foreign)]
+            pub trait [<FFI $name Interceptor>]:
+                $crate::prelude::FFIOperationInterceptor<[<FFI $name OutcomeListener>]>
+            {
+                /// Called with the request right before it is passed to the executor,
+                /// returns the request to pass on, possibly modified.
+                fn [<intercept_ $kind _request>](&self, request: $request) -> $request;
+
+                /// Called with the outcome FFI side notified Rust side about.
+                fn [<intercept_ $kind _outcome>](&self, outcome: [<FFI $name Outcome>]);
+            }
+
+            impl<U: [<FFI $name Interceptor>]>
+                $crate::prelude::FFIOperationInterceptor<[<FFI $name OutcomeListener>]> for U
+            {
+                fn intercept_request(&self, request: $request) -> $request {
+                    self.[<intercept_ $kind _request>](request)
+                }
+
+                fn intercept_outcome(&self, outcome: &[<FFI $name Outcome>]) {
+                    self.[<intercept_ $kind _outcome>](outcome.clone())
+                }
+            }
+
+            impl $crate::prelude::IsOperationRequest for $request {
+                fn set_deadline(
+                    &mut self,
+                    deadline: Option<$crate::prelude::FFIOperationDeadline>,
+                ) {
+                    self.deadline = deadline
+                }
+
+                fn set_operation_id(&mut self, operation_id: u64) {
+                    self.operation_id = Some(operation_id)
+                }
+            }
+        }
+    };
+    (
+        stream
+        $(#[$executor_meta:meta])*
+        $name:ident {
+            kind: $kind:ident,
+            request: $request:ident,
+            output: $value:ty,
+            failure: $failure:ident,
+        }
+    ) => {
+        paste::paste! {
+            #[doc = "The listener FFI side (Swift side) notifies about the values of a `" $name "` stream operation."]
+            #[derive(uniffi::Object)]
+            pub struct [<FFI $name StreamListener>] {
+                stream_listener: $crate::prelude::FFIOperationStreamListener<$value>,
+            }
+
+            impl $crate::prelude::IsListener for [<FFI $name StreamListener>] {
+                type Executor = dyn $crate::prelude::FFIOperationStreamExecutor<Self>;
+                type DispatcherState = $crate::prelude::StreamDispatcherState;
+            }
+
+            impl $crate::prelude::IsStreamListener for [<FFI $name StreamListener>] {
+                const OPERATION_KIND: &'static str = stringify!($kind);
+
+                type Request = $request;
+                type Value = $value;
+                type Failure = $failure;
+            }
+
+            impl From<$crate::prelude::FFIOperationStreamListener<$value>>
+                for [<FFI $name StreamListener>]
+            {
+                fn from(value: $crate::prelude::FFIOperationStreamListener<$value>) -> Self {
+                    Self {
+                        stream_listener: value,
+                    }
+                }
+            }
+
+            #[uniffi::export]
+            impl [<FFI $name StreamListener>] {
+                /// The ID Rust side generated for the dispatch of the operation, the same
+                /// as the `operation_id` of the request.
+                pub fn operation_id(&self) -> u64 {
+                    self.stream_listener.operation_id()
+                }
+
+                /// This is called from FFI Side (Swift side) for every value of the stream,
+                /// failing with `StreamBufferFull` instead of blocking if Rust side has no
+                /// room for it, in which case FFI side notifies the value again later.
+                pub fn notify_value(
+                    &self,
+                    value: $value,
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.stream_listener.notify_value(value)
+                }
+
+                /// This is called from FFI Side (Swift side) for every value of the stream,
+                /// awaiting room for it if Rust side has none.
+                pub async fn notify_value_async(
+                    &self,
+                    value: $value,
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.stream_listener.notify_value_async(value).await
+                }
+
+                /// This is called from FFI Side (Swift side) when the stream has finished
+                /// successfully.
+                pub fn notify_completed(&self) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.stream_listener.notify_completed()
+                }
+
+                /// This is called from FFI Side (Swift side) when the stream has finished
+                /// with `error`.
+                pub fn notify_failed(
+                    &self,
+                    error: $failure,
+                ) -> Result<(), $crate::prelude::NotifyOutcomeError> {
+                    self.stream_listener.notify_failed(error)
+                }
+            }
+
+            $(#[$executor_meta])*
+            #[uniffi::export(with_foreign)]
+            pub trait [<FFI $name Executor>]:
+                $crate::prelude::FFIOperationStreamExecutor<[<FFI $name StreamListener>]>
+            {
+                /// Rust will tell the handler to start streaming values for `request`
+                /// by calling this function, which a concrete type FFI side (Swift side)
+                /// has implemented. It passes back the values using the
+                /// `listener_rust_side` callback, until the stream has finished. If Rust
+                /// side no longer consumes the values it cancels the `cancellation_handle`.
+                fn [<execute_ $kind _request>](
+                    &self,
+                    request: $request,
+                    listener_rust_side: std::sync::Arc<[<FFI $name StreamListener>]>,
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<(), FFISideError>;
+            }
+
+            impl<U: [<FFI $name Executor>]>
+                $crate::prelude::FFIOperationStreamExecutor<[<FFI $name StreamListener>]> for U
+            {
+                fn execute_stream_request(
+                    &self,
+                    request: $request,
+                    listener_rust_side: [<FFI $name StreamListener>],
+                    cancellation_handle: std::sync::Arc<$crate::prelude::FFICancellationHandle>,
+                ) -> Result<(), FFISideError> {
+                    self.[<execute_ $kind _request>](
+                        request,
+                        listener_rust_side.into(),
+                        cancellation_handle,
+                    )
+                }
+            }
+
+            impl $crate::prelude::IsOperationRequest for $request {
+                /// Stream operations have no deadline.
+                fn set_deadline(
+                    &mut self,
+                    _deadline: Option<$crate::prelude::FFIOperationDeadline>,
+                ) {
+                }
+
+                fn set_operation_id(&mut self, operation_id: u64) {
+                    self.operation_id = Some(operation_id)
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use ffi_operation;
+pub(crate) use ffi_operations;
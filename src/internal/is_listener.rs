@@ -0,0 +1,12 @@
+/// A listener a [`FFIOperationDispatcher`] passes to FFI side (Swift side)
+/// together with a request, either listening to the single outcome of an
+/// operation, an [`IsOutcomeListener`], or to the values of a stream
+/// operation, an [`IsStreamListener`].
+pub trait IsListener {
+    /// The executor FFI side implements to carry out this kind of operation.
+    type Executor: ?Sized + Send + Sync;
+
+    /// What a dispatcher of this kind of operation keeps apart from its
+    /// executors, e.g. its interceptors.
+    type DispatcherState: Default + Send + Sync;
+}
@@ -0,0 +1,14 @@
+use crate::prelude::*;
+
+/// A request for an operation to be carried out by FFI side (Swift side),
+/// which Rust side can annotate with information about the dispatch before
+/// passing it to the executor.
+pub trait IsOperationRequest {
+    /// Sets the deadline after which Rust side no longer awaits the outcome
+    /// of the operation, `None` if Rust waits forever.
+    fn set_deadline(&mut self, deadline: Option<FFIOperationDeadline>);
+
+    /// Sets the ID Rust side generated for the dispatch of the operation,
+    /// which FFI side can use to correlate its logs with Rust side.
+    fn set_operation_id(&mut self, operation_id: u64);
+}
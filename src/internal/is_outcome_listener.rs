@@ -1,8 +1,24 @@
 use crate::prelude::*;
 
-pub trait IsOutcomeListener: From<FFIOperationOutcomeListener<Self::Outcome>> {
-    type Request;
-    type Response;
-    type Failure: Into<FFISideError>;
+pub trait IsOutcomeListener:
+    IsListener<
+        Executor = dyn FFIOperationExecutor<Self>,
+        DispatcherState = OutcomeDispatcherState<Self>,
+    > + From<FFIOperationOutcomeListener<Self::Outcome>>
+{
+    /// Name of the kind of operation this listener listens to the outcome of,
+    /// used in errors and diagnostics, e.g. `"networking"`.
+    const OPERATION_KIND: &'static str;
+
+    type Request: IsOperationRequest + IsCoalescableRequest + Clone;
+    type Response: Clone;
+    type Failure: Into<FFISideError> + IsRetryable;
     type Outcome: Into<Result<Self::Response, Self::Failure>>;
+
+    /// The listener FFI side notifies about the outcomes of a batch of
+    /// operations, all at once.
+    type BatchListener: From<FFIOperationOutcomeListener<Vec<Self::Outcome>>>;
+
+    /// What an executor of this kind of operation can advertise it supports.
+    type Capabilities;
 }
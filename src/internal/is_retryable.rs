@@ -0,0 +1,6 @@
+/// Decides whether an operation which failed with `self` should be retried,
+/// if the dispatcher has a [`RetryPolicy`], i.e. whether the failure might
+/// be transient, e.g. a HTTP 503.
+pub trait IsRetryable {
+    fn is_retryable(&self) -> bool;
+}
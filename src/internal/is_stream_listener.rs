@@ -0,0 +1,16 @@
+use crate::prelude::*;
+
+pub trait IsStreamListener:
+    IsListener<
+        Executor = dyn FFIOperationStreamExecutor<Self>,
+        DispatcherState = StreamDispatcherState,
+    > + From<FFIOperationStreamListener<Self::Value>>
+{
+    /// Name of the kind of stream operation this listener listens to the
+    /// values of, used in errors and diagnostics, e.g. `"file_io_read_chunks"`.
+    const OPERATION_KIND: &'static str;
+
+    type Request: IsOperationRequest;
+    type Value;
+    type Failure: Into<FFISideError> + IsRetryable;
+}
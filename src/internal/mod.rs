@@ -1,9 +1,63 @@
+mod async_runtime;
+mod circuit_breaker;
+mod concurrency_limiter;
+mod dispatch_options;
+mod dispatcher_configuration;
+mod dispatcher_stats;
+mod executor_slot;
+mod fallback_trigger;
+mod ffi_cancellation_handle;
+mod ffi_operation_batch_executor;
+mod ffi_operation_deadline;
 mod ffi_operation_dispatcher;
 mod ffi_operation_executor;
+mod ffi_operation_interceptor;
 mod ffi_operation_outcome_listener;
+mod ffi_operation_progress;
+mod ffi_operation_stream;
+mod ffi_operation_stream_executor;
+mod ffi_operation_stream_listener;
+mod ffi_operations_macro;
+mod is_operation_request;
+mod is_listener;
 mod is_outcome_listener;
+mod is_retryable;
+mod is_stream_listener;
+mod operation_id;
+mod operation_options;
+mod request_coalescer;
+mod retry_policy;
+mod served;
+mod unpoisoned;
 
+pub(crate) use async_runtime::*;
+pub use circuit_breaker::*;
+pub use concurrency_limiter::*;
+pub(crate) use dispatch_options::*;
+pub use dispatcher_configuration::*;
+pub use dispatcher_stats::*;
+pub(crate) use executor_slot::*;
+pub use fallback_trigger::*;
+pub use ffi_cancellation_handle::*;
+pub(crate) use ffi_operation_batch_executor::*;
+pub use ffi_operation_deadline::*;
 pub(crate) use ffi_operation_dispatcher::*;
 pub(crate) use ffi_operation_executor::*;
+pub(crate) use ffi_operation_interceptor::*;
 pub(crate) use ffi_operation_outcome_listener::*;
+pub use ffi_operation_progress::*;
+pub(crate) use ffi_operation_stream::*;
+pub(crate) use ffi_operation_stream_executor::*;
+pub(crate) use ffi_operation_stream_listener::*;
+pub(crate) use ffi_operations_macro::*;
+pub(crate) use is_operation_request::*;
+pub(crate) use is_listener::*;
 pub(crate) use is_outcome_listener::*;
+pub(crate) use is_retryable::*;
+pub(crate) use is_stream_listener::*;
+pub(crate) use operation_id::*;
+pub use operation_options::*;
+pub(crate) use request_coalescer::*;
+pub use retry_policy::*;
+pub(crate) use served::*;
+pub(crate) use unpoisoned::*;
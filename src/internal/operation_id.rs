@@ -0,0 +1,8 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Generates a new ID for the dispatch of an operation, unique during the
+/// lifetime of the process.
+pub(crate) fn next_operation_id() -> u64 {
+    static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)
+}
@@ -0,0 +1,35 @@
+use crate::prelude::*;
+
+/// Options FFI side (Swift side) passes to a single call of a client method,
+/// e.g. [`GatewayClient::get_xrd_balance_of_account_with_options`], overriding
+/// the [`DispatcherConfiguration`] of the client for that call only.
+#[derive(Record, Clone, Debug, Default, PartialEq, Eq)]
+pub struct OperationOptions {
+    /// Number of milliseconds the call awaits the outcome, including any
+    /// retries, before failing with [`RustSideError::OperationTimedOut`].
+    /// `timeout_ms` of the configuration still applies to every attempt, so
+    /// the call fails at whichever comes first.
+    #[uniffi(default = None)]
+    pub timeout_ms: Option<u64>,
+
+    /// The priority of the call if it is queued, because the client was
+    /// configured with `max_in_flight`. If `None` the default priority of the
+    /// method applies, which is [`OperationPriority::Normal`] unless documented
+    /// otherwise.
+    #[uniffi(default = None)]
+    pub priority: Option<OperationPriority>,
+}
+
+impl From<OperationOptions> for DispatchOptions {
+    /// The deadline of the call starts now.
+    fn from(value: OperationOptions) -> Self {
+        Self {
+            priority: value.priority.unwrap_or_default(),
+            ..Self::with_deadline(
+                value
+                    .timeout_ms
+                    .map(|timeout_ms| Instant::now() + Duration::from_millis(timeout_ms)),
+            )
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use crate::prelude::*;
+use std::hash::Hash;
+
+/// A request which can share a single execution FFI side (Swift side) with
+/// identical requests dispatched concurrently, if the dispatcher coalesces
+/// requests.
+pub trait IsCoalescableRequest {
+    /// Identifies requests which are identical, e.g. url, method and body of
+    /// a HTTP request.
+    type Key: Hash + Eq + Clone + Send;
+
+    /// The key of this request, `None` if it must never be coalesced.
+    fn coalescing_key(&self) -> Option<Self::Key>;
+}
+
+/// Single-flight deduplication of concurrent operations with the same key,
+/// the first of which is executed, and the others await a clone of its
+/// output.
+pub(crate) struct RequestCoalescer<K, V> {
+    in_flight: Mutex<HashMap<K, Vec<Sender<V>>>>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> RequestCoalescer<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock_in_flight(&self) -> MutexGuard<'_, HashMap<K, Vec<Sender<V>>>> {
+        lock_unpoisoned(&self.in_flight)
+    }
+
+    /// Awaits the output of `operation`, unless an operation with `key` is
+    /// already in flight, in which case its output is awaited instead, if it
+    /// `is_shared`. If that operation is dropped before finishing, e.g.
+    /// because its caller was cancelled, or its output is not shared, one of
+    /// the awaiting callers executes its own operation.
+    pub(crate) async fn coalesce<F, Fut>(
+        &self,
+        key: K,
+        is_shared: impl FnOnce(&V) -> bool,
+        operation: F,
+    ) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        loop {
+            let receiver = {
+                let mut in_flight = self.lock_in_flight();
+                match in_flight.get_mut(&key) {
+                    Some(followers) => {
+                        let (sender, receiver) = channel();
+                        followers.push(sender);
+                        receiver
+                    }
+                    None => {
+                        in_flight.insert(key.clone(), Vec::new());
+                        break;
+                    }
+                }
+            };
+            if let Ok(output) = receiver.await {
+                return output;
+            }
+        }
+
+        let leader = Leader {
+            coalescer: self,
+            key: Some(key),
+        };
+        let output = operation().await;
+        let followers = leader.finish();
+        if is_shared(&output) {
+            for follower in followers {
+                let _ = follower.send(output.clone());
+            }
+        }
+        output
+    }
+}
+
+/// Removes the key of the executed operation once it has finished, or was
+/// dropped, which wakes up the followers so that one of them can take over.
+struct Leader<'a, K: Hash + Eq, V> {
+    coalescer: &'a RequestCoalescer<K, V>,
+    key: Option<K>,
+}
+
+impl<K: Hash + Eq, V> Leader<'_, K, V> {
+    fn remove(&mut self) -> Vec<Sender<V>> {
+        self.key
+            .take()
+            .and_then(|key| lock_unpoisoned(&self.coalescer.in_flight).remove(&key))
+            .unwrap_or_default()
+    }
+
+    fn finish(mut self) -> Vec<Sender<V>> {
+        self.remove()
+    }
+}
+
+impl<K: Hash + Eq, V> Drop for Leader<'_, K, V> {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
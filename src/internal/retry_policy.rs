@@ -0,0 +1,60 @@
+use crate::prelude::*;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// Policy for retrying operations which failed with an [`IsRetryable`] failure,
+/// using exponential backoff with jitter.
+#[derive(Record, Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub max_attempts: u32,
+
+    /// Milliseconds to wait before the first retry.
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound of milliseconds to wait before any retry.
+    pub max_backoff_ms: u64,
+
+    /// Factor the backoff is multiplied with for every retry.
+    pub backoff_multiplier: f64,
+
+    /// Fraction, between `0.0` and `1.0`, of the backoff which is randomized,
+    /// so that many clients do not retry in lockstep.
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 5_000,
+            backoff_multiplier: 2.0,
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait before the retry following the failed `attempt`
+    /// (starting at 1), or `None` if no more attempts should be made.
+    pub(crate) fn backoff_after_attempt(&self, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let exponential = self.initial_backoff_ms as f64
+            * self
+                .backoff_multiplier
+                .max(1.0)
+                .powi(attempt.saturating_sub(1) as i32);
+        let backoff = exponential.min(self.max_backoff_ms as f64);
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+        Some(Duration::from_millis((backoff * (1.0 - jitter)) as u64))
+    }
+}
+
+/// A random number in `[0, 1)`, random enough for jitter.
+fn random_fraction() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,9 @@
+/// The `value` of an operation and which executor of the chain of executors
+/// of a [`FFIOperationDispatcher`] served it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Served<T> {
+    pub(crate) value: T,
+
+    /// The index of the executor in the chain, `0` being the primary one.
+    pub(crate) executor_index: u32,
+}
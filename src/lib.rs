@@ -4,16 +4,31 @@ mod ffibre_error;
 mod internal;
 mod uniffi_exported;
 
+#[cfg(feature = "native-executors")]
+pub mod native_executors;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
 pub mod prelude {
     pub use crate::ffibre_error::*;
     pub(crate) use crate::internal::*;
+    // Exported to FFI side, so also to Rust clients, e.g. tests using `test-utils`.
+    pub use crate::internal::{
+        CircuitBreakerPolicy, CircuitState, DispatcherConfiguration, DispatcherStats,
+        FFICancellationHandle, FFICancellationObserver, FFIOperationDeadline, FFIOperationProgress,
+        FFIOperationProgressObserver, FallbackTrigger, LatencyBucket, LatencyHistogram,
+        OperationOptions, OperationPriority, RetryPolicy,
+    };
     pub use crate::uniffi_exported::*;
 
     pub(crate) use serde::{Deserialize, Serialize};
     pub(crate) use serde_json::to_vec;
     pub(crate) use std::collections::HashMap;
-    pub(crate) use std::sync::{Arc, Mutex};
-    pub(crate) use tokio::sync::oneshot::{channel, Sender};
+    pub(crate) use std::future::Future;
+    pub(crate) use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+    pub(crate) use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    pub(crate) use futures_channel::oneshot::{channel, Sender};
+    pub(crate) use tracing::{debug, info, instrument, warn};
     pub(crate) use uniffi::{export, include_scaffolding, Enum, Error, Object, Record};
 }
 
@@ -0,0 +1,12 @@
+//! Rust side implementations of the executors FFI side (Swift side) otherwise
+//! implements, using `std::fs` and HTTP, so that clients, e.g.
+//! [`GatewayClient`](crate::GatewayClient), can be used from Rust without any
+//! FFI side, e.g. on a server or in a CLI tool.
+
+mod native_file_io_executor;
+mod native_networking_executor;
+mod notify_ignoring_dropped;
+
+pub use native_file_io_executor::*;
+pub use native_networking_executor::*;
+pub(crate) use notify_ignoring_dropped::*;
@@ -0,0 +1,165 @@
+use super::notify_ignoring_dropped;
+use crate::prelude::*;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Makes the temporary files of concurrent atomic writes unique.
+static TEMPORARY_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// A file reader and writer using `std::fs` Rust side, in place of e.g.
+/// `FileManager` FFI side (Swift side), each read and write on a thread of
+/// its own, so that [`FileIOInterface`] can be used from Rust without any FFI
+/// side, passing this executor as both file writer and file reader.
+///
+/// Supports range reads, appending, and atomic writes, by writing to a
+/// temporary file which then replaces the file.
+#[derive(Default)]
+pub struct NativeFileIOExecutor;
+
+impl NativeFileIOExecutor {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FFIFileIOReadExecutor for NativeFileIOExecutor {
+    fn execute_file_io_read_request(
+        &self,
+        request: FFIFileIOReadRequest,
+        listener_rust_side: Arc<FFIFileIOReadOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFIFileIOReadOutcome>, FFISideError> {
+        std::thread::spawn(move || {
+            if cancellation_handle.is_cancelled() {
+                return;
+            }
+            let outcome = match read(&request) {
+                Ok(value) => FFIFileIOReadOutcome::Success { value },
+                Err(error) => FFIFileIOReadOutcome::Failure {
+                    error: FFIFileIOReadError::Unknown {
+                        underlying: error.to_string(),
+                    },
+                },
+            };
+            notify_ignoring_dropped(|| listener_rust_side.notify_outcome(outcome));
+        });
+        Ok(None)
+    }
+
+    fn file_io_read_capabilities(&self) -> Option<FFIFileIOReadCapabilities> {
+        Some(FFIFileIOReadCapabilities {
+            supports_range_read: true,
+        })
+    }
+}
+
+impl FFIFileIOWriteExecutor for NativeFileIOExecutor {
+    fn execute_file_io_write_request(
+        &self,
+        request: FFIFileIOWriteRequest,
+        listener_rust_side: Arc<FFIFileIOWriteOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFIFileIOWriteOutcome>, FFISideError> {
+        std::thread::spawn(move || {
+            if cancellation_handle.is_cancelled() {
+                return;
+            }
+            let total = request.contents.len() as u64;
+            listener_rust_side.notify_progress(0, Some(total));
+            let outcome = match write(&request) {
+                Ok(value) => {
+                    listener_rust_side.notify_progress(total, Some(total));
+                    FFIFileIOWriteOutcome::Success { value }
+                }
+                Err(error) => FFIFileIOWriteOutcome::Failure { error },
+            };
+            notify_ignoring_dropped(|| listener_rust_side.notify_outcome(outcome));
+        });
+        Ok(None)
+    }
+
+    fn file_io_write_capabilities(&self) -> Option<FFIFileIOWriteCapabilities> {
+        Some(FFIFileIOWriteCapabilities {
+            supports_append: true,
+            supports_atomic_write: true,
+        })
+    }
+}
+
+fn read(request: &FFIFileIOReadRequest) -> std::io::Result<FFIFileIOReadResponse> {
+    let mut file = match File::open(&request.absolute_path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            return Ok(FFIFileIOReadResponse::DoesNotExist)
+        }
+        Err(error) => return Err(error),
+    };
+    let mut contents = Vec::new();
+    match request.range {
+        Some(range) => {
+            file.seek(SeekFrom::Start(range.offset))?;
+            file.take(range.length).read_to_end(&mut contents)?;
+        }
+        None => {
+            file.read_to_end(&mut contents)?;
+        }
+    }
+    Ok(FFIFileIOReadResponse::Exists { contents })
+}
+
+fn write(request: &FFIFileIOWriteRequest) -> Result<FFIFileIOWriteResponse, FFIFileIOWriteError> {
+    let path = Path::new(&request.absolute_path);
+    let already_existed = path.exists();
+    let failed_to_write = |error: std::io::Error| FFIFileIOWriteError::FailedToWriteToFileHandle {
+        underlying: error.to_string(),
+    };
+    let failed_to_open = |_| {
+        if already_existed {
+            FFIFileIOWriteError::FailedToGetHandleToFileForWriting
+        } else {
+            FFIFileIOWriteError::FailedToCreateNewFile
+        }
+    };
+
+    match &request.exists_strategy {
+        FileAlreadyExistsStrategy::Abort if already_existed => {
+            return Ok(FFIFileIOWriteResponse::OverwriteAborted)
+        }
+        FileAlreadyExistsStrategy::Append { separator } if already_existed => {
+            let mut file = OpenOptions::new()
+                .append(true)
+                .open(path)
+                .map_err(failed_to_open)?;
+            file.write_all(separator.as_bytes())
+                .and_then(|_| file.write_all(&request.contents))
+                .map_err(failed_to_write)?;
+        }
+        _ if request.atomic => {
+            let file_name = path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy())
+                .unwrap_or_default();
+            let temporary = path.with_file_name(format!(
+                ".{}.{}.{}.tmp",
+                file_name,
+                std::process::id(),
+                TEMPORARY_FILES.fetch_add(1, Ordering::Relaxed)
+            ));
+            let mut file = File::create(&temporary).map_err(failed_to_open)?;
+            file.write_all(&request.contents)
+                .and_then(|_| file.sync_all())
+                .and_then(|_| std::fs::rename(&temporary, path))
+                .map_err(|error| {
+                    let _ = std::fs::remove_file(&temporary);
+                    failed_to_write(error)
+                })?;
+        }
+        _ => {
+            let mut file = File::create(path).map_err(failed_to_open)?;
+            file.write_all(&request.contents).map_err(failed_to_write)?;
+        }
+    }
+    Ok(FFIFileIOWriteResponse::DidWrite { already_existed })
+}
@@ -0,0 +1,105 @@
+use super::notify_ignoring_dropped;
+use crate::prelude::*;
+use std::io::Read;
+
+/// A "network antenna" making HTTP requests Rust side, in place of e.g.
+/// `URLSession` FFI side (Swift side), each request on a thread of its own,
+/// so that [`GatewayClient`] can be used from Rust without any FFI side.
+///
+/// Requests time out at their deadline, if any, but cannot be stopped once
+/// made, so cancelled requests are only not made if not yet started.
+pub struct NativeNetworkingExecutor {
+    agent: ureq::Agent,
+}
+
+impl NativeNetworkingExecutor {
+    pub fn new() -> Self {
+        let mut builder = ureq::AgentBuilder::new();
+        match native_tls::TlsConnector::new() {
+            Ok(tls_connector) => builder = builder.tls_connector(Arc::new(tls_connector)),
+            Err(error) => warn!(%error, "Failed to create TLS connector, HTTPS requests will fail"),
+        }
+        Self {
+            agent: builder.build(),
+        }
+    }
+}
+
+impl Default for NativeNetworkingExecutor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FFINetworkingExecutor for NativeNetworkingExecutor {
+    fn execute_networking_request(
+        &self,
+        request: FFINetworkingRequest,
+        listener_rust_side: Arc<FFINetworkingOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFINetworkingOutcome>, FFISideError> {
+        let agent = self.agent.clone();
+        std::thread::spawn(move || {
+            if cancellation_handle.is_cancelled() {
+                return;
+            }
+            let outcome = match make_request(&agent, &request) {
+                Ok(value) => FFINetworkingOutcome::Success { value },
+                Err(error) => FFINetworkingOutcome::Failure { error },
+            };
+            notify_ignoring_dropped(|| listener_rust_side.notify_outcome(outcome));
+        });
+        Ok(None)
+    }
+
+    fn networking_capabilities(&self) -> Option<FFINetworkingCapabilities> {
+        Some(FFINetworkingCapabilities {
+            supported_methods: ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"]
+                .map(String::from)
+                .to_vec(),
+            max_body_size: None,
+        })
+    }
+}
+
+/// Makes `request`, failing like `URLSession` FFI side does, i.e. with
+/// [`FFINetworkingError::RequestFailed`] for any status code other than 2xx.
+fn make_request(
+    agent: &ureq::Agent,
+    request: &FFINetworkingRequest,
+) -> Result<FFINetworkingResponse, FFINetworkingError> {
+    let mut http_request = agent.request(&request.method, &request.url);
+    for (name, value) in &request.headers {
+        http_request = http_request.set(name, value);
+    }
+    if let Some(deadline) = request.deadline {
+        http_request = http_request.timeout(Duration::from_millis(deadline.timeout_ms));
+    }
+    let failed = |status_code, underlying_error: Option<String>, body: Option<String>| {
+        FFINetworkingError::RequestFailed {
+            status_code,
+            url_session_underlying_error: underlying_error,
+            error_message_from_gateway: body,
+        }
+    };
+    match http_request.send_bytes(&request.body) {
+        Ok(response) => {
+            let status_code = response.status();
+            let mut body = Vec::new();
+            response
+                .into_reader()
+                .read_to_end(&mut body)
+                .map_err(|error| failed(Some(status_code), Some(error.to_string()), None))?;
+            Ok(FFINetworkingResponse { status_code, body })
+        }
+        Err(ureq::Error::Status(status_code, response)) => {
+            Err(failed(Some(status_code), None, response.into_string().ok()))
+        }
+        Err(ureq::Error::Transport(transport)) => match transport.kind() {
+            ureq::ErrorKind::InvalidUrl => Err(FFINetworkingError::FailedToCreateURLFrom {
+                string: request.url.clone(),
+            }),
+            _ => Err(failed(None, Some(transport.to_string()), None)),
+        },
+    }
+}
@@ -0,0 +1,13 @@
+use crate::prelude::*;
+
+/// Notifies Rust side about an outcome using `notify_outcome`, from a thread
+/// of a native executor. Rust side no longer awaiting the outcome, e.g. since
+/// it timed out or was cancelled, is fine, any other failure is logged.
+pub(crate) fn notify_ignoring_dropped(
+    notify_outcome: impl FnOnce() -> Result<(), NotifyOutcomeError>,
+) {
+    match notify_outcome() {
+        Ok(()) | Err(NotifyOutcomeError::NoLongerAwaited { .. }) => {}
+        Err(error) => warn!(%error, "Native executor failed to notify outcome"),
+    }
+}
@@ -0,0 +1,15 @@
+use crate::prelude::*;
+
+/// A successful response of the Gateway to reading the details of an account
+/// holding `amount` XRD.
+pub fn balance_response(amount: &str) -> FFINetworkingOutcome {
+    let body = format!(
+        r#"{{"items":[{{"fungible_resources":{{"items":[{{"amount":"{amount}","resource_address":"resource_rdx1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxradxrd"}}]}}}}]}}"#
+    );
+    FFINetworkingOutcome::Success {
+        value: FFINetworkingResponse {
+            status_code: 200,
+            body: body.into_bytes(),
+        },
+    }
+}
@@ -0,0 +1,385 @@
+use crate::prelude::*;
+use std::collections::VecDeque;
+
+/// A scriptable executor of operations Rust side, in place of an executor FFI
+/// side (Swift side), which responds to every request with the next step of
+/// its script, repeating the last step once the script has run out, and
+/// records every request it was passed, for assertions.
+///
+/// Every step notifies the outcome, or returns it from `execute_*_request`, or
+/// misbehaves, e.g. never notifies or notifies twice, either before
+/// `execute_*_request` returns, or from another thread, optionally after a
+/// delay. A step can also notify progress first.
+pub struct MockExecutor<Request, Outcome, Capabilities> {
+    script: Mutex<VecDeque<MockStep<Outcome>>>,
+    /// The step taken last, repeated once the script has run out.
+    last_step: Mutex<Option<MockStep<Outcome>>>,
+    capabilities: Mutex<Option<Capabilities>>,
+    calls: Mutex<Vec<MockCall<Request>>>,
+    notify_results: Arc<Mutex<Vec<Result<(), NotifyOutcomeError>>>>,
+    /// Notifiers of listeners never notified, kept so that Rust side does not
+    /// see them dropped.
+    never_notified: Mutex<Vec<Notifier<Outcome>>>,
+}
+
+/// A mock of a [`FFINetworkingExecutor`].
+pub type MockNetworkingExecutor =
+    MockExecutor<FFINetworkingRequest, FFINetworkingOutcome, FFINetworkingCapabilities>;
+
+/// A mock of a [`FFIFileIOReadExecutor`].
+pub type MockFileIOReadExecutor =
+    MockExecutor<FFIFileIOReadRequest, FFIFileIOReadOutcome, FFIFileIOReadCapabilities>;
+
+/// A mock of a [`FFIFileIOWriteExecutor`].
+pub type MockFileIOWriteExecutor =
+    MockExecutor<FFIFileIOWriteRequest, FFIFileIOWriteOutcome, FFIFileIOWriteCapabilities>;
+
+type Notifier<Outcome> = Box<dyn Fn(Outcome) -> Result<(), NotifyOutcomeError> + Send>;
+
+type ProgressNotifier = Box<dyn Fn(FFIOperationProgress) + Send>;
+
+/// A step of the script of a [`MockExecutor`].
+#[derive(Clone, Debug)]
+pub struct MockStep<Outcome> {
+    pub behaviour: MockBehaviour<Outcome>,
+
+    /// Notifies from another thread after this delay, if any.
+    pub delay: Option<Duration>,
+
+    /// Notifies from another thread, instead of before `execute_*_request`
+    /// returns, which a delay implies.
+    pub from_another_thread: bool,
+
+    /// Progress notified, in order, before `execute_*_request` returns.
+    pub progress: Vec<FFIOperationProgress>,
+}
+
+/// How a [`MockExecutor`] behaves in a [`MockStep`].
+#[derive(Clone, Debug)]
+pub enum MockBehaviour<Outcome> {
+    /// Notifies the outcome.
+    Notify(Outcome),
+
+    /// Notifies the outcome twice, the second time failing with
+    /// [`NotifyOutcomeError::AlreadyNotified`].
+    NotifyTwice(Outcome),
+
+    /// Never notifies, keeping the listener alive, so that Rust side awaits
+    /// the outcome until it times out, if ever.
+    NeverNotify,
+
+    /// Drops the listener without notifying.
+    DropListener,
+
+    /// Throws, i.e. fails `execute_*_request`.
+    Throw(FFISideError),
+
+    /// Returns the outcome from `execute_*_request`, without notifying it.
+    Return(Outcome),
+
+    /// Returns the `returned` outcome from `execute_*_request`, and also
+    /// notifies the `notified` outcome, which Rust side ignores.
+    ReturnAndNotify {
+        returned: Outcome,
+        notified: Outcome,
+    },
+
+    /// Returns the outcome from `execute_*_request`, keeping the listener
+    /// like [`MockBehaviour::NeverNotify`], so that the test can notify it
+    /// after Rust side has used the returned outcome.
+    ReturnAndKeepListener(Outcome),
+}
+
+impl<Outcome> MockStep<Outcome> {
+    pub fn new(behaviour: MockBehaviour<Outcome>) -> Self {
+        Self {
+            behaviour,
+            delay: None,
+            from_another_thread: false,
+            progress: Vec::new(),
+        }
+    }
+
+    /// Notifies from another thread after `delay`.
+    pub fn after(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Notifies from another thread.
+    pub fn from_another_thread(mut self) -> Self {
+        self.from_another_thread = true;
+        self
+    }
+
+    /// Notifies `progress`, in order, before `execute_*_request` returns.
+    pub fn with_progress(
+        mut self,
+        progress: impl IntoIterator<Item = FFIOperationProgress>,
+    ) -> Self {
+        self.progress = progress.into_iter().collect();
+        self
+    }
+}
+
+/// A request passed to a [`MockExecutor`].
+#[derive(Clone)]
+pub struct MockCall<Request> {
+    pub request: Request,
+    pub cancellation_handle: Arc<FFICancellationHandle>,
+}
+
+impl<Request, Outcome, Capabilities> Default for MockExecutor<Request, Outcome, Capabilities> {
+    fn default() -> Self {
+        Self {
+            script: Mutex::new(VecDeque::new()),
+            last_step: Mutex::new(None),
+            capabilities: Mutex::new(None),
+            calls: Mutex::new(Vec::new()),
+            notify_results: Arc::new(Mutex::new(Vec::new())),
+            never_notified: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<Request, Outcome, Capabilities> MockExecutor<Request, Outcome, Capabilities>
+where
+    Request: Clone,
+    Outcome: Clone + Send + 'static,
+    Capabilities: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `step` to the script.
+    pub fn then(&self, step: MockStep<Outcome>) -> &Self {
+        lock_unpoisoned(&self.script).push_back(step);
+        self
+    }
+
+    /// Appends notifying `outcome` to the script.
+    pub fn respond(&self, outcome: Outcome) -> &Self {
+        self.then(MockStep::new(MockBehaviour::Notify(outcome)))
+    }
+
+    /// Appends notifying `outcome` from another thread after `delay` to the script.
+    pub fn respond_after(&self, delay: Duration, outcome: Outcome) -> &Self {
+        self.then(MockStep::new(MockBehaviour::Notify(outcome)).after(delay))
+    }
+
+    /// Appends notifying `outcome` from another thread to the script.
+    pub fn respond_from_another_thread(&self, outcome: Outcome) -> &Self {
+        self.then(MockStep::new(MockBehaviour::Notify(outcome)).from_another_thread())
+    }
+
+    /// Appends notifying `outcome` twice to the script.
+    pub fn respond_twice(&self, outcome: Outcome) -> &Self {
+        self.then(MockStep::new(MockBehaviour::NotifyTwice(outcome)))
+    }
+
+    /// Appends never notifying to the script.
+    pub fn never_respond(&self) -> &Self {
+        self.then(MockStep::new(MockBehaviour::NeverNotify))
+    }
+
+    /// Appends dropping the listener without notifying to the script.
+    pub fn drop_listener(&self) -> &Self {
+        self.then(MockStep::new(MockBehaviour::DropListener))
+    }
+
+    /// Appends throwing `error` to the script.
+    pub fn throw(&self, error: impl Into<FFISideError>) -> &Self {
+        self.then(MockStep::new(MockBehaviour::Throw(error.into())))
+    }
+
+    /// Appends returning `outcome` from `execute_*_request` to the script.
+    pub fn respond_immediately(&self, outcome: Outcome) -> &Self {
+        self.then(MockStep::new(MockBehaviour::Return(outcome)))
+    }
+
+    /// Advertises `capabilities`, by default `None`.
+    pub fn set_capabilities(&self, capabilities: Option<Capabilities>) -> &Self {
+        *lock_unpoisoned(&self.capabilities) = capabilities;
+        self
+    }
+
+    /// Every request passed to this executor so far, in order.
+    pub fn calls(&self) -> Vec<MockCall<Request>> {
+        lock_unpoisoned(&self.calls).clone()
+    }
+
+    /// Every request passed to this executor so far, in order.
+    pub fn requests(&self) -> Vec<Request> {
+        lock_unpoisoned(&self.calls)
+            .iter()
+            .map(|call| call.request.clone())
+            .collect()
+    }
+
+    /// What notifying Rust side returned for every notified outcome so far,
+    /// e.g. [`NotifyOutcomeError::AlreadyNotified`] for the second outcome of
+    /// [`MockBehaviour::NotifyTwice`].
+    pub fn notify_results(&self) -> Vec<Result<(), NotifyOutcomeError>> {
+        lock_unpoisoned(&self.notify_results).clone()
+    }
+
+    /// Notifies `outcome` to every listener kept by a [`MockBehaviour::NeverNotify`]
+    /// or [`MockBehaviour::ReturnAndKeepListener`] step so far, e.g. to finish
+    /// an operation once the test has seen it in flight.
+    pub fn notify_pending(&self, outcome: Outcome) {
+        let pending = std::mem::take(&mut *lock_unpoisoned(&self.never_notified));
+        for notify in pending {
+            let result = notify(outcome.clone());
+            lock_unpoisoned(&self.notify_results).push(result);
+        }
+    }
+
+    /// Drops every listener kept by a [`MockBehaviour::NeverNotify`] or
+    /// [`MockBehaviour::ReturnAndKeepListener`] step so far without notifying
+    /// it, like FFI side losing track of them.
+    pub fn drop_pending(&self) {
+        drop(std::mem::take(&mut *lock_unpoisoned(&self.never_notified)));
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        lock_unpoisoned(&self.capabilities).clone()
+    }
+
+    fn execute(
+        &self,
+        request: Request,
+        cancellation_handle: Arc<FFICancellationHandle>,
+        notify: Notifier<Outcome>,
+        notify_progress: ProgressNotifier,
+    ) -> Result<Option<Outcome>, FFISideError> {
+        lock_unpoisoned(&self.calls).push(MockCall {
+            request,
+            cancellation_handle,
+        });
+
+        let step = {
+            let mut last_step = lock_unpoisoned(&self.last_step);
+            if let Some(step) = lock_unpoisoned(&self.script).pop_front() {
+                *last_step = Some(step);
+            }
+            last_step.clone()
+        }
+        .expect("MockExecutor has no scripted step, script one before dispatching");
+
+        for progress in step.progress.iter().copied() {
+            notify_progress(progress)
+        }
+
+        let (outcome, times, returned) = match step.behaviour {
+            MockBehaviour::Notify(outcome) => (outcome, 1, None),
+            MockBehaviour::NotifyTwice(outcome) => (outcome, 2, None),
+            MockBehaviour::NeverNotify => {
+                lock_unpoisoned(&self.never_notified).push(notify);
+                return Ok(None);
+            }
+            MockBehaviour::DropListener => return Ok(None),
+            MockBehaviour::Throw(error) => return Err(error),
+            MockBehaviour::Return(outcome) => return Ok(Some(outcome)),
+            MockBehaviour::ReturnAndKeepListener(outcome) => {
+                lock_unpoisoned(&self.never_notified).push(notify);
+                return Ok(Some(outcome));
+            }
+            MockBehaviour::ReturnAndNotify { returned, notified } => (notified, 1, Some(returned)),
+        };
+
+        let notify_results = self.notify_results.clone();
+        let notify_outcome = move || {
+            for _ in 0..times {
+                let result = notify(outcome.clone());
+                lock_unpoisoned(&notify_results).push(result);
+            }
+        };
+        if step.delay.is_some() || step.from_another_thread {
+            std::thread::spawn(move || {
+                if let Some(delay) = step.delay {
+                    std::thread::sleep(delay);
+                }
+                notify_outcome()
+            });
+        } else {
+            notify_outcome()
+        }
+        Ok(returned)
+    }
+}
+
+impl FFINetworkingExecutor for MockNetworkingExecutor {
+    fn execute_networking_request(
+        &self,
+        request: FFINetworkingRequest,
+        listener_rust_side: Arc<FFINetworkingOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFINetworkingOutcome>, FFISideError> {
+        self.execute(
+            request,
+            cancellation_handle,
+            Box::new({
+                let listener_rust_side = listener_rust_side.clone();
+                move |outcome| listener_rust_side.notify_outcome(outcome)
+            }),
+            Box::new(move |progress| {
+                listener_rust_side.notify_progress(progress.completed, progress.total)
+            }),
+        )
+    }
+
+    fn networking_capabilities(&self) -> Option<FFINetworkingCapabilities> {
+        self.capabilities()
+    }
+}
+
+impl FFIFileIOReadExecutor for MockFileIOReadExecutor {
+    fn execute_file_io_read_request(
+        &self,
+        request: FFIFileIOReadRequest,
+        listener_rust_side: Arc<FFIFileIOReadOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFIFileIOReadOutcome>, FFISideError> {
+        self.execute(
+            request,
+            cancellation_handle,
+            Box::new({
+                let listener_rust_side = listener_rust_side.clone();
+                move |outcome| listener_rust_side.notify_outcome(outcome)
+            }),
+            Box::new(move |progress| {
+                listener_rust_side.notify_progress(progress.completed, progress.total)
+            }),
+        )
+    }
+
+    fn file_io_read_capabilities(&self) -> Option<FFIFileIOReadCapabilities> {
+        self.capabilities()
+    }
+}
+
+impl FFIFileIOWriteExecutor for MockFileIOWriteExecutor {
+    fn execute_file_io_write_request(
+        &self,
+        request: FFIFileIOWriteRequest,
+        listener_rust_side: Arc<FFIFileIOWriteOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFIFileIOWriteOutcome>, FFISideError> {
+        self.execute(
+            request,
+            cancellation_handle,
+            Box::new({
+                let listener_rust_side = listener_rust_side.clone();
+                move |outcome| listener_rust_side.notify_outcome(outcome)
+            }),
+            Box::new(move |progress| {
+                listener_rust_side.notify_progress(progress.completed, progress.total)
+            }),
+        )
+    }
+
+    fn file_io_write_capabilities(&self) -> Option<FFIFileIOWriteCapabilities> {
+        self.capabilities()
+    }
+}
@@ -0,0 +1,13 @@
+//! Rust side implementations of the executors FFI side (Swift side) otherwise
+//! implements, so that clients, e.g. [`GatewayClient`](crate::GatewayClient),
+//! can be tested in plain `cargo test`, without Swift or Kotlin toolchains.
+
+mod gateway_responses;
+mod mock_executor;
+mod recording_host_bug_hook;
+mod recording_progress_observer;
+
+pub use gateway_responses::*;
+pub use mock_executor::*;
+pub use recording_host_bug_hook::*;
+pub use recording_progress_observer::*;
@@ -0,0 +1,36 @@
+use crate::prelude::*;
+
+/// A [`FFIHostBugHook`] recording all host bugs reported to it, for
+/// assertions. The hook is global, so tests look only at the bugs of their
+/// own operations, using [`RecordingHostBugHook::bugs_of`].
+#[derive(Default)]
+pub struct RecordingHostBugHook {
+    bugs: Mutex<Vec<RustSideError>>,
+}
+
+impl RecordingHostBugHook {
+    /// Installs a new hook, replacing any previously installed one.
+    pub fn install() -> Arc<Self> {
+        let hook = Arc::new(Self::default());
+        install_host_bug_hook(hook.clone());
+        hook
+    }
+
+    /// All host bugs of the operation with `operation_id` reported so far.
+    pub fn bugs_of(&self, operation_id: u64) -> Vec<RustSideError> {
+        lock_unpoisoned(&self.bugs)
+            .iter()
+            .filter(|bug| {
+                matches!(bug, RustSideError::ListenerDroppedWithoutOutcome { operation_id: id, .. }
+                    if *id == operation_id)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl FFIHostBugHook for RecordingHostBugHook {
+    fn on_host_bug(&self, bug: RustSideError) {
+        lock_unpoisoned(&self.bugs).push(bug)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::prelude::*;
+
+/// A [`FFIOperationProgressObserver`] recording all progress it observes, for
+/// assertions.
+#[derive(Default)]
+pub struct RecordingProgressObserver {
+    progress: Mutex<Vec<FFIOperationProgress>>,
+}
+
+impl RecordingProgressObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All progress observed so far, in order.
+    pub fn progress(&self) -> Vec<FFIOperationProgress> {
+        lock_unpoisoned(&self.progress).clone()
+    }
+}
+
+impl FFIOperationProgressObserver for RecordingProgressObserver {
+    fn on_progress(&self, progress: FFIOperationProgress) {
+        lock_unpoisoned(&self.progress).push(progress)
+    }
+}
@@ -1,4 +1,5 @@
 use tokio::time;
+use tracing::{info_span, Instrument};
 
 use crate::prelude::*;
 
@@ -10,7 +11,10 @@ pub trait IsPublisher<T>: Send + Sync {
 #[uniffi::export(with_foreign)]
 pub trait IsTransactionPublisher: IsPublisher<Transaction> {
     fn on_value(&self, value: Transaction);
-    fn rust_is_subscribed_notify_cancellation_on(&self, listener: Arc<CancellationListener>);
+    fn rust_is_subscribed_notify_cancellation_on(
+        &self,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    );
     fn finished_from_rust_side(&self);
 }
 
@@ -31,16 +35,15 @@ impl GatewayClient {
         self: Arc<Self>, // must use `Arc<Self>` to not have to deal with tricky send/sync of self
         publisher: Arc<dyn IsTransactionPublisher>,
     ) {
-        let (sender, receiver) = channel::<()>();
-
-        let cancellation_listener = Arc::new(CancellationListener::new(sender));
-        publisher.rust_is_subscribed_notify_cancellation_on(cancellation_listener);
+        let cancellation_handle = Arc::new(FFICancellationHandle::new());
+        publisher.rust_is_subscribed_notify_cancellation_on(cancellation_handle.clone());
 
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
             .build()
             .unwrap();
 
+        let span = info_span!("subscribe_stream_of_latest_transactions");
         runtime.block_on(async {
             tokio::select! {
                 _ = async {
@@ -52,7 +55,7 @@ impl GatewayClient {
                             last_tx_id = value.tx_id.clone();
                             publisher.publish_value(value);
                         } else {
-                            println!("🐌 Ignored duplicate value (no new TX done yet...)")
+                            debug!(tx_id = last_tx_id, "Ignored duplicate value, no new transaction yet")
                         }
                         let delay = time::Duration::from_secs(5);
                         tokio::time::sleep(delay).await;
@@ -60,9 +63,9 @@ impl GatewayClient {
                 } => {
                     // loop finished?
                 }
-                _ = async { receiver.await } => { println!("❌ RUST loop async fn received cancellation from Swift side => cancelling") }
+                _ = cancellation_handle.cancelled() => { info!("Received cancellation from Swift side, cancelling subscription") }
             }
-        });
+        }.instrument(span));
     }
 
     pub async fn get_latest_transactions_or_panic(&self) -> Transaction {
@@ -74,32 +77,3 @@ impl GatewayClient {
             .clone()
     }
 }
-
-////////
-
-#[derive(Object)]
-pub struct CancellationListener {
-    sender: Mutex<Option<Sender<()>>>,
-}
-
-impl CancellationListener {
-    pub(crate) fn new(sender: Sender<()>) -> Self {
-        Self {
-            sender: Mutex::new(Some(sender)),
-        }
-    }
-}
-#[export]
-impl CancellationListener {
-    pub fn notify_cancelled(&self) {
-        println!("❌ RUST received cancellation from Swift");
-        self.sender
-            .lock()
-            .expect("Should only have access sender Mutex once.")
-            .take()
-            .expect("You MUST NOT call `notify_cancelled` twice in Swift.")
-            .send(())
-            .map_err(|_| RustSideError::FailedToPropagateResultFromFFIOperationBackToDispatcher)
-            .expect("Must never fail, since some context's in FFI side cannot be throwing.")
-    }
-}
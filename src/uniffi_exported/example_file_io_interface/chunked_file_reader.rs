@@ -0,0 +1,63 @@
+use crate::prelude::*;
+
+/// Reads files in chunks FFI side (Swift side), consuming the chunks as a
+/// stream Rust side, as soon as they have been read.
+#[derive(Object)]
+pub struct ChunkedFileReader {
+    chunk_reader: FFIOperationDispatcher<FFIFileIOReadChunksStreamListener>,
+}
+
+#[export]
+impl ChunkedFileReader {
+    #[uniffi::constructor]
+    pub fn new(file_chunk_reader: Arc<dyn FFIFileIOReadChunksExecutor>) -> Self {
+        Self {
+            chunk_reader: FFIOperationDispatcher::<FFIFileIOReadChunksStreamListener>::new(
+                file_chunk_reader,
+            ),
+        }
+    }
+
+    /// Replaces the executor of all chunked file reads with `file_chunk_reader`,
+    /// reads in flight finish on the replaced one.
+    pub fn replace_executor(&self, file_chunk_reader: Arc<dyn FFIFileIOReadChunksExecutor>) {
+        self.chunk_reader.replace_executor(file_chunk_reader)
+    }
+
+    /// A snapshot of the stats of all chunked file reads of this reader, e.g.
+    /// for a diagnostics screen.
+    pub fn stats(&self) -> DispatcherStats {
+        self.chunk_reader.stats()
+    }
+
+    /// Reads the contents of the file at `file_absolute_path`, in chunks of at
+    /// most `chunk_size` bytes.
+    pub async fn read(
+        &self,
+        file_absolute_path: String,
+        chunk_size: u64,
+    ) -> Result<Vec<u8>, FFIBridgeError> {
+        let mut chunks = self
+            .chunk_reader
+            .dispatch_stream(FFIFileIOReadChunksRequest::new(
+                file_absolute_path,
+                chunk_size,
+            ))?;
+
+        let mut contents = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            contents.extend(chunk?);
+        }
+        Ok(contents)
+    }
+
+    /// Blocking variant of [`ChunkedFileReader::read`], for FFI side without
+    /// async bindings.
+    pub fn read_blocking(
+        &self,
+        file_absolute_path: String,
+        chunk_size: u64,
+    ) -> Result<Vec<u8>, FFIBridgeError> {
+        block_on(self.read(file_absolute_path, chunk_size))?
+    }
+}
@@ -19,6 +19,217 @@ impl FileIOInterface {
         }
     }
 
+    /// Constructs a new [`FileIOInterface`] which dispatches both reads and
+    /// writes according to `configuration`, e.g. with a timeout.
+    #[uniffi::constructor]
+    pub fn with_configuration(
+        file_writer: Arc<dyn FFIFileIOWriteExecutor>,
+        file_reader: Arc<dyn FFIFileIOReadExecutor>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self {
+            writer: FFIOperationDispatcher::<FFIFileIOWriteOutcomeListener>::with_configuration(
+                file_writer,
+                configuration.clone(),
+            ),
+            reader: FFIOperationDispatcher::<FFIFileIOReadOutcomeListener>::with_configuration(
+                file_reader,
+                configuration,
+            ),
+        }
+    }
+
+    /// Constructs a new [`FileIOInterface`] like [`FileIOInterface::with_configuration`]
+    /// does, but passing reads and writes on to the `fallback_file_readers` and
+    /// `fallback_file_writers` respectively, in order, if the one before fails
+    /// with a failure `configuration` falls back on, e.g. from an on-disk file
+    /// reader to one reading bundled resources.
+    #[uniffi::constructor]
+    pub fn with_fallback_executors(
+        file_writer: Arc<dyn FFIFileIOWriteExecutor>,
+        file_reader: Arc<dyn FFIFileIOReadExecutor>,
+        fallback_file_writers: Vec<Arc<dyn FFIFileIOWriteExecutor>>,
+        fallback_file_readers: Vec<Arc<dyn FFIFileIOReadExecutor>>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self {
+            writer:
+                FFIOperationDispatcher::<FFIFileIOWriteOutcomeListener>::with_fallback_executors(
+                    file_writer,
+                    fallback_file_writers
+                        .into_iter()
+                        .map(|file_writer| file_writer as _)
+                        .collect(),
+                    configuration.clone(),
+                ),
+            reader: FFIOperationDispatcher::<FFIFileIOReadOutcomeListener>::with_fallback_executors(
+                file_reader,
+                fallback_file_readers
+                    .into_iter()
+                    .map(|file_reader| file_reader as _)
+                    .collect(),
+                configuration,
+            ),
+        }
+    }
+
+    /// Replaces the executor of all file reads with `file_reader`, file reads
+    /// in flight finish on the replaced one.
+    pub fn replace_read_executor(&self, file_reader: Arc<dyn FFIFileIOReadExecutor>) {
+        self.reader.replace_executor(file_reader)
+    }
+
+    /// Replaces the executor of all file writes with `file_writer`, file writes
+    /// in flight finish on the replaced one.
+    pub fn replace_write_executor(&self, file_writer: Arc<dyn FFIFileIOWriteExecutor>) {
+        self.writer.replace_executor(file_writer)
+    }
+
+    /// Adds `interceptor` of all file reads, e.g. for logging or metrics.
+    pub fn add_read_interceptor(&self, interceptor: Arc<dyn FFIFileIOReadInterceptor>) {
+        self.reader.add_interceptor(interceptor)
+    }
+
+    /// Adds `interceptor` of all file writes, e.g. for logging or metrics.
+    pub fn add_write_interceptor(&self, interceptor: Arc<dyn FFIFileIOWriteInterceptor>) {
+        self.writer.add_interceptor(interceptor)
+    }
+
+    /// Sets `batch_executor` to carry out all reads of many files, using
+    /// [`FileIOInterface::read_files`], in a single FFI crossing.
+    pub fn set_read_batch_executor(&self, batch_executor: Arc<dyn FFIFileIOReadBatchExecutor>) {
+        self.reader.set_batch_executor(batch_executor)
+    }
+
+    /// A snapshot of the stats of all file reads.
+    pub fn read_stats(&self) -> DispatcherStats {
+        self.reader.stats()
+    }
+
+    /// A snapshot of the stats of all file writes.
+    pub fn write_stats(&self) -> DispatcherStats {
+        self.writer.stats()
+    }
+
+    /// Reads the contents of all files at `file_absolute_paths`, in a single FFI
+    /// crossing if a read batch executor has been set, returning the contents
+    /// of every file, `None` if it does not exist, or why reading it failed.
+    pub async fn read_files(&self, file_absolute_paths: Vec<String>) -> Vec<FileContentsResult> {
+        self.read_files_with_options(file_absolute_paths, OperationOptions::default())
+            .await
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_files`], for FFI side without
+    /// async bindings.
+    pub fn read_files_blocking(
+        &self,
+        file_absolute_paths: Vec<String>,
+    ) -> Result<Vec<FileContentsResult>, FFIBridgeError> {
+        Ok(block_on(self.read_files(file_absolute_paths))?)
+    }
+
+    /// Like [`FileIOInterface::read_files`], but according to `options`, e.g.
+    /// with a timeout all reads must finish within.
+    pub async fn read_files_with_options(
+        &self,
+        file_absolute_paths: Vec<String>,
+        options: OperationOptions,
+    ) -> Vec<FileContentsResult> {
+        self.reader
+            .dispatch_batch(
+                file_absolute_paths
+                    .into_iter()
+                    .map(FFIFileIOReadRequest::new)
+                    .collect(),
+                options.into(),
+            )
+            .await
+            .into_iter()
+            .map(|result| match result {
+                Ok(response) => FileContentsResult::Success {
+                    contents: response.into(),
+                },
+                Err(error) => FileContentsResult::Failure { error },
+            })
+            .collect()
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_files_with_options`], for
+    /// FFI side without async bindings.
+    pub fn read_files_with_options_blocking(
+        &self,
+        file_absolute_paths: Vec<String>,
+        options: OperationOptions,
+    ) -> Result<Vec<FileContentsResult>, FFIBridgeError> {
+        Ok(block_on(
+            self.read_files_with_options(file_absolute_paths, options),
+        )?)
+    }
+
+    /// Reads the bytes in `range` of the file at `file_absolute_path`, `None`
+    /// if it does not exist. Only the range is read if the executor supports
+    /// range reads, else the whole file is read and sliced Rust side.
+    pub async fn read_range(
+        &self,
+        file_absolute_path: String,
+        range: FFIByteRange,
+    ) -> Result<Option<Vec<u8>>, FFIBridgeError> {
+        self.read_range_with_options(file_absolute_path, range, OperationOptions::default())
+            .await
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_range`], for FFI side without
+    /// async bindings.
+    pub fn read_range_blocking(
+        &self,
+        file_absolute_path: String,
+        range: FFIByteRange,
+    ) -> Result<Option<Vec<u8>>, FFIBridgeError> {
+        block_on(self.read_range(file_absolute_path, range))?
+    }
+
+    /// Like [`FileIOInterface::read_range`], but according to `options`, e.g.
+    /// with a timeout of its own.
+    pub async fn read_range_with_options(
+        &self,
+        file_absolute_path: String,
+        range: FFIByteRange,
+        options: OperationOptions,
+    ) -> Result<Option<Vec<u8>>, FFIBridgeError> {
+        // Any executor of the chain might serve the read.
+        let supports_range_read = self.reader.executors().iter().all(|executor| {
+            executor
+                .capabilities()
+                .is_some_and(|capabilities| capabilities.supports_range_read)
+        });
+
+        let mut request = FFIFileIOReadRequest::new(file_absolute_path);
+        if supports_range_read {
+            request.range = Some(range);
+        }
+        let contents: Option<Vec<u8>> = self
+            .reader
+            .dispatch_with_options(request, options.into())
+            .await?
+            .into();
+        if supports_range_read {
+            Ok(contents)
+        } else {
+            Ok(contents.map(|contents| range.slice(&contents)))
+        }
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_range_with_options`], for
+    /// FFI side without async bindings.
+    pub fn read_range_with_options_blocking(
+        &self,
+        file_absolute_path: String,
+        range: FFIByteRange,
+        options: OperationOptions,
+    ) -> Result<Option<Vec<u8>>, FFIBridgeError> {
+        block_on(self.read_range_with_options(file_absolute_path, range, options))?
+    }
+
     pub(crate) async fn read(
         &self,
         file_absolute_path: String,
@@ -30,6 +241,56 @@ impl FileIOInterface {
         Ok(response.into())
     }
 
+    /// Reads the contents of the file at `file_absolute_path`, `None` if it
+    /// does not exist, and returns which file reader read the file, e.g. to
+    /// tell whether it was read from disk or from bundled resources.
+    pub async fn read_served(
+        &self,
+        file_absolute_path: String,
+    ) -> Result<ServedFileContents, FFIBridgeError> {
+        self.read_served_with_options(file_absolute_path, OperationOptions::default())
+            .await
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_served`], for FFI side
+    /// without async bindings.
+    pub fn read_served_blocking(
+        &self,
+        file_absolute_path: String,
+    ) -> Result<ServedFileContents, FFIBridgeError> {
+        block_on(self.read_served(file_absolute_path))?
+    }
+
+    /// Like [`FileIOInterface::read_served`], but according to `options`, e.g.
+    /// with a timeout of its own.
+    pub async fn read_served_with_options(
+        &self,
+        file_absolute_path: String,
+        options: OperationOptions,
+    ) -> Result<ServedFileContents, FFIBridgeError> {
+        let served = self
+            .reader
+            .dispatch_served(
+                FFIFileIOReadRequest::new(file_absolute_path),
+                options.into(),
+            )
+            .await?;
+        Ok(ServedFileContents {
+            contents: served.value.into(),
+            served_by: served.executor_index,
+        })
+    }
+
+    /// Blocking variant of [`FileIOInterface::read_served_with_options`], for
+    /// FFI side without async bindings.
+    pub fn read_served_with_options_blocking(
+        &self,
+        file_absolute_path: String,
+        options: OperationOptions,
+    ) -> Result<ServedFileContents, FFIBridgeError> {
+        block_on(self.read_served_with_options(file_absolute_path, options))?
+    }
+
     pub(crate) async fn write(
         &self,
         file_absolute_path: String,
@@ -51,8 +312,154 @@ impl FileIOInterface {
         extend_strategy: ExtendExistingFileStrategy,
         contents: Vec<u8>,
     ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        self.extend_file(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+            DispatchOptions::default(),
+        )
+        .await
+    }
+
+    /// Blocking variant of [`FileIOInterface::write_to_new_or_extend_existing_file`],
+    /// for FFI side without async bindings.
+    pub fn write_to_new_or_extend_existing_file_blocking(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        block_on(self.write_to_new_or_extend_existing_file(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+        ))?
+    }
+
+    /// Like [`FileIOInterface::write_to_new_or_extend_existing_file`], but
+    /// according to `options`, e.g. with a timeout the read and the write
+    /// together must finish within.
+    pub async fn write_to_new_or_extend_existing_file_with_options(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+        options: OperationOptions,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        self.extend_file(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+            options.into(),
+        )
+        .await
+    }
+
+    /// Blocking variant of
+    /// [`FileIOInterface::write_to_new_or_extend_existing_file_with_options`],
+    /// for FFI side without async bindings.
+    pub fn write_to_new_or_extend_existing_file_with_options_blocking(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+        options: OperationOptions,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        block_on(self.write_to_new_or_extend_existing_file_with_options(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+            options,
+        ))?
+    }
+
+    /// Like [`FileIOInterface::write_to_new_or_extend_existing_file`], but lets
+    /// `progress_observer` observe the progress of the write, if FFI side
+    /// reports it, e.g. to drive a progress bar.
+    pub async fn write_to_new_or_extend_existing_file_with_progress(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+        progress_observer: Arc<dyn FFIOperationProgressObserver>,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        self.extend_file(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+            DispatchOptions {
+                progress_observer: Some(progress_observer),
+                ..DispatchOptions::default()
+            },
+        )
+        .await
+    }
+
+    /// Blocking variant of
+    /// [`FileIOInterface::write_to_new_or_extend_existing_file_with_progress`],
+    /// for FFI side without async bindings.
+    pub fn write_to_new_or_extend_existing_file_with_progress_blocking(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+        progress_observer: Arc<dyn FFIOperationProgressObserver>,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        block_on(self.write_to_new_or_extend_existing_file_with_progress(
+            file_absolute_path,
+            extend_strategy,
+            contents,
+            progress_observer,
+        ))?
+    }
+}
+
+impl FileIOInterface {
+    async fn extend_file(
+        &self,
+        file_absolute_path: String,
+        extend_strategy: ExtendExistingFileStrategy,
+        contents: Vec<u8>,
+        mut options: DispatchOptions,
+    ) -> Result<FFIFileIOWriteResponse, FFIBridgeError> {
+        // The read and the write together must finish within the timeout.
+        if options.deadline.is_none() {
+            options.deadline = self
+                .writer
+                .configuration
+                .timeout()
+                .map(|timeout| Instant::now() + timeout);
+        }
+        let deadline = options.deadline;
+        // Any executor of the chain might serve the write.
+        let capabilities = self
+            .writer
+            .executors()
+            .iter()
+            .map(|executor| executor.capabilities())
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        let all_support = |supports: fn(&FFIFileIOWriteCapabilities) -> bool| {
+            !capabilities.is_empty() && capabilities.iter().all(supports)
+        };
+
+        // Let FFI side append natively, instead of reading and rewriting the file.
+        if let ExtendExistingFileStrategy::Append { separator } = &extend_strategy {
+            if all_support(|capabilities| capabilities.supports_append) {
+                let request = FFIFileIOWriteRequest::new(
+                    file_absolute_path,
+                    contents,
+                    FileAlreadyExistsStrategy::Append {
+                        separator: separator.clone(),
+                    },
+                );
+                return self.writer.dispatch_with_options(request, options).await;
+            }
+        }
+
         let mut contents = contents;
-        contents = self.read(file_absolute_path.clone()).await.map(|r| {
+        let existing = self.read_before(file_absolute_path.clone(), deadline);
+        contents = existing.await.map(|r| {
             if let Some(mut existing) = r {
                 match extend_strategy {
                     ExtendExistingFileStrategy::Append { separator } => {
@@ -71,12 +478,29 @@ impl FileIOInterface {
             }
         })?;
 
-        self.write(
+        // Never leave a half rewritten file behind, if FFI side can avoid it.
+        let mut request = FFIFileIOWriteRequest::new(
             file_absolute_path,
             contents,
             FileAlreadyExistsStrategy::Overwrite,
-        )
-        .await
+        );
+        request.atomic = all_support(|capabilities| capabilities.supports_atomic_write);
+        self.writer.dispatch_with_options(request, options).await
+    }
+
+    async fn read_before(
+        &self,
+        file_absolute_path: String,
+        deadline: Option<Instant>,
+    ) -> Result<Option<Vec<u8>>, FFIBridgeError> {
+        let response = self
+            .reader
+            .dispatch_with_options(
+                FFIFileIOReadRequest::new(file_absolute_path),
+                DispatchOptions::with_deadline(deadline),
+            )
+            .await?;
+        Ok(response.into())
     }
 }
 
@@ -85,3 +509,21 @@ pub enum ExtendExistingFileStrategy {
     Append { separator: String },
     Prepend { separator: String },
 }
+
+/// The outcome of reading one of many files using [`FileIOInterface::read_files`].
+#[derive(Enum, Clone, Debug, PartialEq, Eq)]
+pub enum FileContentsResult {
+    Success { contents: Option<Vec<u8>> },
+    Failure { error: FFIBridgeError },
+}
+
+/// The contents of a file, `None` if it does not exist, and which file reader
+/// of a [`FileIOInterface`] read it.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct ServedFileContents {
+    pub contents: Option<Vec<u8>>,
+
+    /// The index of the file reader, `0` being the primary one, followed by
+    /// the fallback ones in order.
+    pub served_by: u32,
+}
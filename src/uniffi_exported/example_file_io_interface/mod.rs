@@ -1,3 +1,5 @@
+mod chunked_file_reader;
 mod file_io_interface;
 
+pub use chunked_file_reader::*;
 pub use file_io_interface::*;
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use futures_util::future::join_all;
 
 /// A [Radix][https://www.radixdlt.com/] Gateway REST client, that makes its
 /// network request using a "network antenna" 'installed' from FFI Side (Swift side).
@@ -21,6 +22,59 @@ impl GatewayClient {
         }
     }
 
+    /// Constructs a new [`GatewayClient`] like [`GatewayClient::new`] does, but
+    /// dispatching network requests according to `configuration`, e.g. with a
+    /// timeout.
+    #[uniffi::constructor]
+    pub fn with_configuration(
+        network_antenna: Arc<dyn FFINetworkingExecutor>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self {
+            networking_dispatcher:
+                FFIOperationDispatcher::<FFINetworkingOutcomeListener>::with_configuration(
+                    network_antenna,
+                    configuration,
+                ),
+        }
+    }
+
+    /// Constructs a new [`GatewayClient`] like [`GatewayClient::with_configuration`]
+    /// does, but passing network requests on to the `fallback_network_antennas`,
+    /// in order, if the one before fails with a failure `configuration` falls
+    /// back on, e.g. a `[Swift]URLSession` backed by a proxy.
+    #[uniffi::constructor]
+    pub fn with_fallback_executors(
+        network_antenna: Arc<dyn FFINetworkingExecutor>,
+        fallback_network_antennas: Vec<Arc<dyn FFINetworkingExecutor>>,
+        configuration: DispatcherConfiguration,
+    ) -> Self {
+        Self {
+            networking_dispatcher:
+                FFIOperationDispatcher::<FFINetworkingOutcomeListener>::with_fallback_executors(
+                    network_antenna,
+                    fallback_network_antennas
+                        .into_iter()
+                        .map(|network_antenna| network_antenna as _)
+                        .collect(),
+                    configuration,
+                ),
+        }
+    }
+
+    /// Replaces the "network antenna" of this client with `network_antenna`,
+    /// e.g. a `[Swift]URLSession` with another proxy or auth configuration
+    /// after login. Network requests in flight finish on the replaced one.
+    pub fn replace_executor(&self, network_antenna: Arc<dyn FFINetworkingExecutor>) {
+        self.networking_dispatcher.replace_executor(network_antenna)
+    }
+
+    /// Adds `interceptor` of all network requests made by this client, e.g. for
+    /// header injection, logging or metrics.
+    pub fn add_interceptor(&self, interceptor: Arc<dyn FFINetworkingInterceptor>) {
+        self.networking_dispatcher.add_interceptor(interceptor)
+    }
+
     /// Reads the XRD balance of a Radix account with `[address]`, the actual
     /// network call is being done FFI Side (Swift side), but the parsing of JSON
     /// into models, and mapping of models [`GetEntityDetailsResponse`] ->
@@ -28,26 +82,242 @@ impl GatewayClient {
     pub async fn get_xrd_balance_of_account(
         &self,
         address: String,
+    ) -> Result<String, FFIBridgeError> {
+        self.get_xrd_balance_of_account_with_options(address, OperationOptions::default())
+            .await
+    }
+
+    /// Like [`GatewayClient::get_xrd_balance_of_account`], but according to
+    /// `options`, e.g. with a timeout of its own.
+    pub async fn get_xrd_balance_of_account_with_options(
+        &self,
+        address: String,
+        options: OperationOptions,
     ) -> Result<String, FFIBridgeError> {
         self.post(
             "state/entity/details",
             GetEntityDetailsRequest::new(address),
             parse_xrd_balance_from,
+            options.into(),
         )
         .await
     }
 
+    /// Blocking variant of [`GatewayClient::get_xrd_balance_of_account_with_options`],
+    /// for FFI side without async bindings.
+    pub fn get_xrd_balance_of_account_with_options_blocking(
+        &self,
+        address: String,
+        options: OperationOptions,
+    ) -> Result<String, FFIBridgeError> {
+        block_on(self.get_xrd_balance_of_account_with_options(address, options))?
+    }
+
+    /// Like [`GatewayClient::get_xrd_balance_of_account`], but also returns
+    /// which "network antenna" made the network request.
+    pub async fn get_xrd_balance_of_account_served(
+        &self,
+        address: String,
+    ) -> Result<ServedXrdBalance, FFIBridgeError> {
+        self.get_xrd_balance_of_account_served_with_options(address, OperationOptions::default())
+            .await
+    }
+
+    /// Blocking variant of [`GatewayClient::get_xrd_balance_of_account_served`],
+    /// for FFI side without async bindings.
+    pub fn get_xrd_balance_of_account_served_blocking(
+        &self,
+        address: String,
+    ) -> Result<ServedXrdBalance, FFIBridgeError> {
+        block_on(self.get_xrd_balance_of_account_served(address))?
+    }
+
+    /// Like [`GatewayClient::get_xrd_balance_of_account_served`], but according
+    /// to `options`, e.g. with a timeout of its own.
+    pub async fn get_xrd_balance_of_account_served_with_options(
+        &self,
+        address: String,
+        options: OperationOptions,
+    ) -> Result<ServedXrdBalance, FFIBridgeError> {
+        let served = self
+            .make_request(
+                "state/entity/details",
+                "POST",
+                GetEntityDetailsRequest::new(address),
+                parse_xrd_balance_from,
+                options.into(),
+            )
+            .await?;
+        Ok(ServedXrdBalance {
+            balance: served.value,
+            served_by: served.executor_index,
+        })
+    }
+
+    /// Blocking variant of
+    /// [`GatewayClient::get_xrd_balance_of_account_served_with_options`], for
+    /// FFI side without async bindings.
+    pub fn get_xrd_balance_of_account_served_with_options_blocking(
+        &self,
+        address: String,
+        options: OperationOptions,
+    ) -> Result<ServedXrdBalance, FFIBridgeError> {
+        block_on(self.get_xrd_balance_of_account_served_with_options(address, options))?
+    }
+
+    /// Blocking variant of [`GatewayClient::get_xrd_balance_of_account`], for
+    /// FFI side without async bindings.
+    pub fn get_xrd_balance_of_account_blocking(
+        &self,
+        address: String,
+    ) -> Result<String, FFIBridgeError> {
+        block_on(self.get_xrd_balance_of_account(address))?
+    }
+
+    /// Reads the XRD balances of many Radix accounts, in the same order as
+    /// `addresses`. The requests are made with low priority, unless the
+    /// `priority` of the options says otherwise, so that they queue up behind
+    /// other requests if the client was configured with `max_in_flight`.
+    pub async fn get_xrd_balances_of_accounts(
+        &self,
+        addresses: Vec<String>,
+    ) -> Result<Vec<String>, FFIBridgeError> {
+        self.get_xrd_balances_of_accounts_with_options(addresses, OperationOptions::default())
+            .await
+    }
+
+    /// Like [`GatewayClient::get_xrd_balances_of_accounts`], but according to
+    /// `options`, e.g. with a timeout all requests must finish within.
+    pub async fn get_xrd_balances_of_accounts_with_options(
+        &self,
+        addresses: Vec<String>,
+        options: OperationOptions,
+    ) -> Result<Vec<String>, FFIBridgeError> {
+        let options = DispatchOptions::from(OperationOptions {
+            priority: options.priority.or(Some(OperationPriority::Low)),
+            ..options
+        });
+        join_all(addresses.into_iter().map(|address| {
+            self.post(
+                "state/entity/details",
+                GetEntityDetailsRequest::new(address),
+                parse_xrd_balance_from,
+                options.clone(),
+            )
+        }))
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    /// Blocking variant of [`GatewayClient::get_xrd_balances_of_accounts`], for
+    /// FFI side without async bindings.
+    pub fn get_xrd_balances_of_accounts_blocking(
+        &self,
+        addresses: Vec<String>,
+    ) -> Result<Vec<String>, FFIBridgeError> {
+        block_on(self.get_xrd_balances_of_accounts(addresses))?
+    }
+
+    /// Blocking variant of [`GatewayClient::get_xrd_balances_of_accounts_with_options`],
+    /// for FFI side without async bindings.
+    pub fn get_xrd_balances_of_accounts_with_options_blocking(
+        &self,
+        addresses: Vec<String>,
+        options: OperationOptions,
+    ) -> Result<Vec<String>, FFIBridgeError> {
+        block_on(self.get_xrd_balances_of_accounts_with_options(addresses, options))?
+    }
+
+    /// A snapshot of the stats of all network requests made by this client,
+    /// e.g. for a diagnostics screen.
+    pub fn stats(&self) -> DispatcherStats {
+        self.networking_dispatcher.stats()
+    }
+
+    /// The state of the circuit breaker of this client, e.g. for showing an
+    /// offline banner while it is not [`CircuitState::Closed`].
+    pub fn circuit_state(&self) -> CircuitState {
+        self.networking_dispatcher.circuit_state()
+    }
+
+    /// The number of network requests queued, because `max_in_flight` network
+    /// requests are already being made FFI side.
+    pub fn queue_depth(&self) -> u64 {
+        self.networking_dispatcher.queue_depth() as u64
+    }
+
     pub async fn get_latest_transactions(&self) -> Result<Vec<Transaction>, FFIBridgeError> {
+        self.get_latest_transactions_with_options(OperationOptions::default())
+            .await
+    }
+
+    /// Blocking variant of [`GatewayClient::get_latest_transactions`], for FFI
+    /// side without async bindings.
+    pub fn get_latest_transactions_blocking(&self) -> Result<Vec<Transaction>, FFIBridgeError> {
+        block_on(self.get_latest_transactions())?
+    }
+
+    /// Like [`GatewayClient::get_latest_transactions`], but according to
+    /// `options`, e.g. with a timeout of its own.
+    pub async fn get_latest_transactions_with_options(
+        &self,
+        options: OperationOptions,
+    ) -> Result<Vec<Transaction>, FFIBridgeError> {
         self.post(
             "stream/transactions",
             GetTransactionStreamRequest::default(),
             parse_transactions,
+            options.into(),
         )
         .await
     }
+
+    /// Blocking variant of [`GatewayClient::get_latest_transactions_with_options`],
+    /// for FFI side without async bindings.
+    pub fn get_latest_transactions_with_options_blocking(
+        &self,
+        options: OperationOptions,
+    ) -> Result<Vec<Transaction>, FFIBridgeError> {
+        block_on(self.get_latest_transactions_with_options(options))?
+    }
+
+    /// Like [`GatewayClient::get_latest_transactions`], but lets
+    /// `progress_observer` observe the progress of the download, if the
+    /// "network antenna" reports it, e.g. to drive a progress bar.
+    pub async fn get_latest_transactions_with_progress(
+        &self,
+        progress_observer: Arc<dyn FFIOperationProgressObserver>,
+    ) -> Result<Vec<Transaction>, FFIBridgeError> {
+        self.post(
+            "stream/transactions",
+            GetTransactionStreamRequest::default(),
+            parse_transactions,
+            DispatchOptions {
+                progress_observer: Some(progress_observer),
+                ..DispatchOptions::default()
+            },
+        )
+        .await
+    }
+
+    /// Blocking variant of [`GatewayClient::get_latest_transactions_with_progress`],
+    /// for FFI side without async bindings.
+    pub fn get_latest_transactions_with_progress_blocking(
+        &self,
+        progress_observer: Arc<dyn FFIOperationProgressObserver>,
+    ) -> Result<Vec<Transaction>, FFIBridgeError> {
+        block_on(self.get_latest_transactions_with_progress(progress_observer))?
+    }
 }
 
 impl GatewayClient {
+    #[instrument(
+        level = "debug",
+        name = "parse_response",
+        skip_all,
+        fields(status_code = response.status_code, type_name = std::any::type_name::<U>())
+    )]
     fn model_from_response<U>(&self, response: FFINetworkingResponse) -> Result<U, RustSideError>
     where
         U: for<'a> Deserialize<'a>,
@@ -55,34 +325,43 @@ impl GatewayClient {
         if let 200..=299 = response.status_code {
             // all good
         } else {
+            warn!("Bad response code");
             return Err(RustSideError::BadResponseCode);
         }
 
         let body = response.body;
         if body.is_empty() {
-            return Err(RustSideError::ResponseBodyWasNil.into());
+            warn!("Response body was empty");
+            return Err(RustSideError::ResponseBodyWasNil);
         }
 
-        serde_json::from_slice::<U>(&body).map_err(|_| {
+        serde_json::from_slice::<U>(&body).map_err(|error| {
+            warn!(%error, "Unable to JSON deserialize response body");
             RustSideError::UnableJSONDeserializeHTTPResponseBodyIntoTypeName {
                 type_name: std::any::type_name::<U>().to_owned(),
             }
         })
     }
 
+    /// Makes the request, every error it fails with, also Rust side before or
+    /// after dispatching it, carrying the ID of the operation.
     async fn make_request<T, U, V, F, E>(
         &self,
         path: impl AsRef<str>,
         method: impl AsRef<str>,
         request: T,
         map: F,
-    ) -> Result<V, FFIBridgeError>
+        mut options: DispatchOptions,
+    ) -> Result<Served<V>, FFIBridgeError>
     where
         T: Serialize,
         U: for<'a> Deserialize<'a>,
         F: Fn(U) -> Result<V, E>,
         E: Into<FFIBridgeError>,
     {
+        let operation_id = *options.operation_id.get_or_insert_with(next_operation_id);
+        let failed = |error: FFIBridgeError| error.with_operation_id(operation_id);
+
         // JSON serialize request into body bytes
         let body = to_vec(&request).unwrap();
 
@@ -99,18 +378,48 @@ impl GatewayClient {
                 "Content-Type".to_owned(),
                 "application/json".to_owned(),
             )]),
+            deadline: None,
+            operation_id: Some(operation_id),
         };
 
+        // Fail early if every "network antenna" advertises it cannot make it.
+        let mut unsupported_reasons = self
+            .networking_dispatcher
+            .executors()
+            .into_iter()
+            .map(|executor| {
+                executor
+                    .capabilities()
+                    .and_then(|capabilities| capabilities.unsupported_reason(&request))
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        if !unsupported_reasons.is_empty() {
+            return Err(failed(
+                RustSideError::UnsupportedByExecutor {
+                    reason: unsupported_reasons.swap_remove(0),
+                }
+                .into(),
+            ));
+        }
+
         // Let Swift side make network request and await response
-        let response = self.networking_dispatcher.dispatch(request).await?;
+        let served = self
+            .networking_dispatcher
+            .dispatch_served(request, options)
+            .await?;
 
         // Read out HTTP body from response and JSON parse it into U
         let model = self
-            .model_from_response(response)
-            .map_err(|error| FFIBridgeError::FromRust { error })?;
+            .model_from_response(served.value)
+            .map_err(|error| failed(error.into()))?;
 
         // Map U -> V
-        map(model).map_err(|e| e.into())
+        let value = map(model).map_err(|error| failed(error.into()))?;
+        Ok(Served {
+            value,
+            executor_index: served.executor_index,
+        })
     }
 
     pub(crate) async fn post<T, U, V, F, E>(
@@ -118,6 +427,7 @@ impl GatewayClient {
         path: impl AsRef<str>,
         request: T,
         map: F,
+        options: DispatchOptions,
     ) -> Result<V, FFIBridgeError>
     where
         T: Serialize,
@@ -125,6 +435,19 @@ impl GatewayClient {
         F: Fn(U) -> Result<V, E>,
         E: Into<FFIBridgeError>,
     {
-        self.make_request(path, "POST", request, map).await
+        self.make_request(path, "POST", request, map, options)
+            .await
+            .map(|served| served.value)
     }
 }
+
+/// The XRD balance of an account and which "network antenna" of a
+/// [`GatewayClient`] made the network request reading it.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct ServedXrdBalance {
+    pub balance: String,
+
+    /// The index of the "network antenna", `0` being the primary one, followed
+    /// by the fallback ones in order.
+    pub served_by: u32,
+}
@@ -0,0 +1,38 @@
+use crate::prelude::*;
+use std::sync::RwLock;
+
+/// Flags bugs FFI side (Swift side) in debug builds, implemented FFI side,
+/// e.g. with an `assertionFailure`, so that they are caught during
+/// development instead of surfacing as vague errors in production.
+#[uniffi::export(with_foreign)]
+pub trait FFIHostBugHook: Send + Sync {
+    /// Called with the error the operation failed with due to a bug FFI
+    /// side, e.g. [`RustSideError::ListenerDroppedWithoutOutcome`].
+    fn on_host_bug(&self, bug: RustSideError);
+}
+
+/// Installs `hook` to be called for every bug FFI side Rust side detects,
+/// replacing any previously installed hook. Only called in debug builds,
+/// bugs are logged in all builds.
+#[export]
+pub fn install_host_bug_hook(hook: Arc<dyn FFIHostBugHook>) {
+    *write_unpoisoned(&INSTALLED_HOST_BUG_HOOK) = Some(hook);
+}
+
+static INSTALLED_HOST_BUG_HOOK: RwLock<Option<Arc<dyn FFIHostBugHook>>> = RwLock::new(None);
+
+/// Logs `error` an operation failed with if it is due to a bug FFI side, and
+/// in debug builds calls the installed [`FFIHostBugHook`], if any.
+pub(crate) fn report_if_host_bug(error: &RustSideError) {
+    if !error.is_host_bug() {
+        return;
+    }
+    warn!(bug = %error, "Bug FFI side");
+    if !cfg!(debug_assertions) {
+        return;
+    }
+    let hook = read_unpoisoned(&INSTALLED_HOST_BUG_HOOK).clone();
+    if let Some(hook) = hook {
+        hook.on_host_bug(error.clone())
+    }
+}
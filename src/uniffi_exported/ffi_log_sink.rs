@@ -0,0 +1,177 @@
+use crate::prelude::*;
+use std::sync::RwLock;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record as SpanRecord};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// The severity of a [`FFILogRecord`], from least to most severe.
+#[derive(Enum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FFILogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<&Level> for FFILogLevel {
+    fn from(value: &Level) -> Self {
+        match *value {
+            Level::TRACE => Self::Trace,
+            Level::DEBUG => Self::Debug,
+            Level::INFO => Self::Info,
+            Level::WARN => Self::Warn,
+            Level::ERROR => Self::Error,
+        }
+    }
+}
+
+/// A structured log record Rust side passes to the [`FFILogSink`] installed by
+/// FFI side (Swift side), e.g. to be routed into `os_log` or Logcat.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct FFILogRecord {
+    pub level: FFILogLevel,
+
+    /// The module path Rust side which logged the record, e.g.
+    /// `"ffibre::internal::ffi_operation_dispatcher"`.
+    pub target: String,
+
+    pub message: String,
+
+    /// The fields of the record and of all spans it was logged in, e.g. the
+    /// `operation` and `operation_id` of the operation being dispatched.
+    pub fields: HashMap<String, String>,
+}
+
+/// Receives all log records of Rust side, implemented FFI side (Swift side),
+/// which MUST NOT call back into Rust side when doing so.
+#[uniffi::export(with_foreign)]
+pub trait FFILogSink: Send + Sync {
+    /// Called on the thread Rust side logged `record` on.
+    fn log(&self, record: FFILogRecord);
+}
+
+/// Installs `sink` to receive all log records of Rust side of at least
+/// `min_level`, replacing any previously installed sink.
+///
+/// Has no effect if a Rust side host has already installed another global
+/// `tracing` subscriber.
+#[export]
+pub fn install_log_sink(sink: Arc<dyn FFILogSink>, min_level: FFILogLevel) {
+    static SUBSCRIBER: OnceLock<()> = OnceLock::new();
+    *write_unpoisoned(&INSTALLED_LOG_SINK) = Some((sink, min_level));
+    SUBSCRIBER.get_or_init(|| {
+        let subscriber = tracing_subscriber::registry().with(FFILogSinkLayer);
+        _ = tracing::subscriber::set_global_default(subscriber);
+    });
+}
+
+static INSTALLED_LOG_SINK: RwLock<Option<(Arc<dyn FFILogSink>, FFILogLevel)>> = RwLock::new(None);
+
+fn installed_log_sink() -> Option<(Arc<dyn FFILogSink>, FFILogLevel)> {
+    read_unpoisoned(&INSTALLED_LOG_SINK).clone()
+}
+
+/// Forwards all `tracing` events to the installed [`FFILogSink`], together
+/// with the fields of the spans they were recorded in.
+struct FFILogSinkLayer;
+
+/// The fields of a span, stored in its extensions.
+struct SpanFields(HashMap<String, String>);
+
+impl<S> Layer<S> for FFILogSinkLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // The min level can change when a sink is installed, so never cache.
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        installed_log_sink().is_some_and(|(_, min_level)| {
+            // Spans of any level, so that records within them carry their fields.
+            metadata.is_span() || FFILogLevel::from(metadata.level()) >= min_level
+        })
+    }
+
+    fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldsVisitor::default();
+        attributes.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &SpanRecord<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldsVisitor::default();
+        values.record(&mut visitor);
+        let mut extensions = span.extensions_mut();
+        if let Some(fields) = extensions.get_mut::<SpanFields>() {
+            fields.0.extend(visitor.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some((sink, min_level)) = installed_log_sink() else {
+            return;
+        };
+        let level = FFILogLevel::from(event.metadata().level());
+        if level < min_level {
+            return;
+        }
+
+        // Outermost span first, so that inner fields win.
+        let mut visitor = FieldsVisitor::default();
+        for span in ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+        {
+            if let Some(fields) = span.extensions().get::<SpanFields>() {
+                visitor.fields.extend(fields.0.clone());
+            }
+        }
+        event.record(&mut visitor);
+
+        sink.log(FFILogRecord {
+            level,
+            target: event.metadata().target().to_owned(),
+            message: visitor.message.unwrap_or_default(),
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[derive(Default)]
+struct FieldsVisitor {
+    message: Option<String>,
+    fields: HashMap<String, String>,
+}
+
+impl FieldsVisitor {
+    fn insert(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.insert(field.name().to_owned(), value);
+        }
+    }
+}
+
+impl Visit for FieldsVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.to_owned())
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{:?}", value))
+    }
+}
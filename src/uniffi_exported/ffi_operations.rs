@@ -0,0 +1,40 @@
+use crate::prelude::*;
+
+ffi_operations! {
+    /// A "network antenna" FFI side (Swift side), e.g. `URLSession`, which
+    /// makes HTTP requests on behalf of Rust side.
+    Networking {
+        kind: networking,
+        request: FFINetworkingRequest,
+        response: FFINetworkingResponse,
+        failure: FFINetworkingError,
+        capabilities: FFINetworkingCapabilities,
+    },
+
+    /// Reads files FFI side (Swift side), e.g. using `FileHandle`.
+    FileIORead {
+        kind: file_io_read,
+        request: FFIFileIOReadRequest,
+        response: FFIFileIOReadResponse,
+        failure: FFIFileIOReadError,
+        capabilities: FFIFileIOReadCapabilities,
+    },
+
+    /// Reads files FFI side (Swift side) in chunks, e.g. using `FileHandle`,
+    /// passing back every chunk as soon as it has been read.
+    FileIOReadChunks {
+        kind: file_io_read_chunks,
+        request: FFIFileIOReadChunksRequest,
+        stream: Vec<u8>,
+        failure: FFIFileIOReadChunksError,
+    },
+
+    /// Writes files FFI side (Swift side), e.g. using `FileHandle`.
+    FileIOWrite {
+        kind: file_io_write,
+        request: FFIFileIOWriteRequest,
+        response: FFIFileIOWriteResponse,
+        failure: FFIFileIOWriteError,
+        capabilities: FFIFileIOWriteCapabilities,
+    },
+}
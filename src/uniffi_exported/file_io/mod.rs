@@ -1,5 +1,7 @@
 mod read;
+mod read_chunks;
 mod write;
 
 pub use read::*;
+pub use read_chunks::*;
 pub use write::*;
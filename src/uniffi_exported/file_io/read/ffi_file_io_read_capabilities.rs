@@ -0,0 +1,9 @@
+use crate::prelude::*;
+
+/// What a file reader FFI Side (Swift side) supports.
+#[derive(Record, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FFIFileIOReadCapabilities {
+    /// If it can read only the `range` of a [`FFIFileIOReadRequest`], instead
+    /// of Rust side reading the whole file and slicing it.
+    pub supports_range_read: bool,
+}
@@ -6,3 +6,9 @@ pub enum FFIFileIOReadError {
     #[error("UnknownError: '{underlying}'")]
     Unknown { underlying: String },
 }
+
+impl IsRetryable for FFIFileIOReadError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
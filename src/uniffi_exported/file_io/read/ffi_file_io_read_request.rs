@@ -3,10 +3,48 @@ use crate::prelude::*;
 #[derive(Record, Clone, Debug, PartialEq, Eq)]
 pub struct FFIFileIOReadRequest {
     pub absolute_path: String,
+
+    /// The range of bytes of the file to read, if not the whole file, only set
+    /// if the executor supports range reads.
+    pub range: Option<FFIByteRange>,
+
+    pub deadline: Option<FFIOperationDeadline>,
+    pub operation_id: Option<u64>,
 }
 
 impl FFIFileIOReadRequest {
     pub fn new(absolute_path: String) -> Self {
-        Self { absolute_path }
+        Self {
+            absolute_path,
+            range: None,
+            deadline: None,
+            operation_id: None,
+        }
+    }
+}
+
+impl IsCoalescableRequest for FFIFileIOReadRequest {
+    type Key = (String, Option<FFIByteRange>);
+
+    fn coalescing_key(&self) -> Option<Self::Key> {
+        Some((self.absolute_path.clone(), self.range))
+    }
+}
+
+/// A range of `length` bytes starting at byte `offset`.
+#[derive(Record, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FFIByteRange {
+    pub offset: u64,
+    pub length: u64,
+}
+
+impl FFIByteRange {
+    /// The bytes of `contents` in this range, clamped to its length.
+    pub(crate) fn slice(&self, contents: &[u8]) -> Vec<u8> {
+        let start = (self.offset as usize).min(contents.len());
+        let end = start
+            .saturating_add(self.length as usize)
+            .min(contents.len());
+        contents[start..end].to_vec()
     }
 }
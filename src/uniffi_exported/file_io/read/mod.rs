@@ -1,13 +1,9 @@
+mod ffi_file_io_read_capabilities;
 mod ffi_file_io_read_error;
-mod ffi_file_io_read_executor;
-mod ffi_file_io_read_outcome;
-mod ffi_file_io_read_outcome_listener;
 mod ffi_file_io_read_request;
 mod ffi_file_io_read_response;
 
+pub use ffi_file_io_read_capabilities::*;
 pub use ffi_file_io_read_error::*;
-pub use ffi_file_io_read_executor::*;
-pub use ffi_file_io_read_outcome::*;
-pub use ffi_file_io_read_outcome_listener::*;
 pub use ffi_file_io_read_request::*;
 pub use ffi_file_io_read_response::*;
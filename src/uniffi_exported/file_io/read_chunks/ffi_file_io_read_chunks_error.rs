@@ -0,0 +1,17 @@
+use crate::prelude::*;
+use thiserror::Error as ThisError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Error, ThisError)]
+pub enum FFIFileIOReadChunksError {
+    #[error("File does not exist")]
+    DoesNotExist,
+
+    #[error("UnknownError: '{underlying}'")]
+    Unknown { underlying: String },
+}
+
+impl IsRetryable for FFIFileIOReadChunksError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
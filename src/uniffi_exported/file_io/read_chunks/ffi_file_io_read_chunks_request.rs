@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct FFIFileIOReadChunksRequest {
+    pub absolute_path: String,
+
+    /// The max number of bytes of every chunk FFI side passes back.
+    pub chunk_size: u64,
+
+    /// The ID Rust side generated for the dispatch of the operation.
+    pub operation_id: Option<u64>,
+}
+
+impl FFIFileIOReadChunksRequest {
+    pub fn new(absolute_path: String, chunk_size: u64) -> Self {
+        Self {
+            absolute_path,
+            chunk_size,
+            operation_id: None,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod ffi_file_io_read_chunks_error;
+mod ffi_file_io_read_chunks_request;
+
+pub use ffi_file_io_read_chunks_error::*;
+pub use ffi_file_io_read_chunks_request::*;
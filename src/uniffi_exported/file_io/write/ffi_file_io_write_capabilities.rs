@@ -0,0 +1,15 @@
+use crate::prelude::*;
+
+/// What a file writer FFI Side (Swift side) supports.
+#[derive(Record, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FFIFileIOWriteCapabilities {
+    /// If it can append to an existing file, using
+    /// [`FileAlreadyExistsStrategy::Append`], instead of Rust side reading the
+    /// file and rewriting it.
+    pub supports_append: bool,
+
+    /// If it can write a file atomically, when the `atomic` flag of a
+    /// [`FFIFileIOWriteRequest`] is set, e.g. to a temporary file which then
+    /// replaces the file.
+    pub supports_atomic_write: bool,
+}
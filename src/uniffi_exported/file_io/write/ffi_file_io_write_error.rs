@@ -12,3 +12,9 @@ pub enum FFIFileIOWriteError {
     #[error("Failed to write to file handle: '{underlying}'")]
     FailedToWriteToFileHandle { underlying: String },
 }
+
+impl IsRetryable for FFIFileIOWriteError {
+    fn is_retryable(&self) -> bool {
+        false
+    }
+}
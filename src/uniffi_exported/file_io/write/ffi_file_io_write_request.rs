@@ -4,6 +4,10 @@ use crate::prelude::*;
 pub enum FileAlreadyExistsStrategy {
     Overwrite,
     Abort,
+
+    /// Appends `separator` and then the contents to the existing file, only
+    /// used if the executor supports appending.
+    Append { separator: String },
 }
 
 #[derive(Record, Clone, Debug, PartialEq, Eq)]
@@ -11,6 +15,13 @@ pub struct FFIFileIOWriteRequest {
     pub absolute_path: String,
     pub contents: Vec<u8>,
     pub exists_strategy: FileAlreadyExistsStrategy,
+
+    /// If the file should be written atomically, only set if the executor
+    /// supports atomic writes.
+    pub atomic: bool,
+
+    pub deadline: Option<FFIOperationDeadline>,
+    pub operation_id: Option<u64>,
 }
 
 impl FFIFileIOWriteRequest {
@@ -23,6 +34,18 @@ impl FFIFileIOWriteRequest {
             absolute_path,
             contents,
             exists_strategy,
+            atomic: false,
+            deadline: None,
+            operation_id: None,
         }
     }
 }
+
+impl IsCoalescableRequest for FFIFileIOWriteRequest {
+    type Key = ();
+
+    /// Writes have side effects, so are never coalesced.
+    fn coalescing_key(&self) -> Option<Self::Key> {
+        None
+    }
+}
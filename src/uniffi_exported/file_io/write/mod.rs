@@ -1,13 +1,9 @@
+mod ffi_file_io_write_capabilities;
 mod ffi_file_io_write_error;
-mod ffi_file_io_write_executor;
-mod ffi_file_io_write_outcome;
-mod ffi_file_io_write_outcome_listener;
 mod ffi_file_io_write_request;
 mod ffi_file_io_write_response;
 
+pub use ffi_file_io_write_capabilities::*;
 pub use ffi_file_io_write_error::*;
-pub use ffi_file_io_write_executor::*;
-pub use ffi_file_io_write_outcome::*;
-pub use ffi_file_io_write_outcome_listener::*;
 pub use ffi_file_io_write_request::*;
 pub use ffi_file_io_write_response::*;
@@ -1,10 +1,17 @@
+#[cfg(feature = "tokio")]
 mod example_async_stream_from_rust;
 mod example_file_io_interface;
 mod example_gateway;
+mod ffi_host_bug_hook;
+mod ffi_log_sink;
+mod ffi_operations;
 mod file_io;
 mod networking;
 
 pub use example_file_io_interface::*;
 pub use example_gateway::*;
+pub use ffi_host_bug_hook::*;
+pub use ffi_log_sink::*;
+pub use ffi_operations::*;
 pub use file_io::*;
 pub use networking::*;
@@ -0,0 +1,34 @@
+use crate::prelude::*;
+
+/// What a "network antenna" FFI Side (Swift side) supports, so that Rust side
+/// can fail early instead of making requests it cannot make.
+#[derive(Record, Clone, Debug, PartialEq, Eq)]
+pub struct FFINetworkingCapabilities {
+    /// The HTTP methods it can make requests with, e.g. `["GET", "POST"]`.
+    pub supported_methods: Vec<String>,
+
+    /// The max size in bytes of the body of a request, if limited.
+    pub max_body_size: Option<u64>,
+}
+
+impl FFINetworkingCapabilities {
+    /// Why `request` cannot be made by the executor with these capabilities,
+    /// if it cannot.
+    pub(crate) fn unsupported_reason(&self, request: &FFINetworkingRequest) -> Option<String> {
+        if !self
+            .supported_methods
+            .iter()
+            .any(|method| method.eq_ignore_ascii_case(&request.method))
+        {
+            return Some(format!("HTTP method '{}' is not supported", request.method));
+        }
+        match self.max_body_size {
+            Some(max_body_size) if request.body.len() as u64 > max_body_size => Some(format!(
+                "Body of {} bytes exceeds max body size of {} bytes",
+                request.body.len(),
+                max_body_size
+            )),
+            _ => None,
+        }
+    }
+}
@@ -18,3 +18,17 @@ pub enum FFINetworkingError {
         error_message_from_gateway: Option<String>,
     },
 }
+
+impl IsRetryable for FFINetworkingError {
+    /// Requests which failed without any response, e.g. due to lost
+    /// connectivity, or with a server error, e.g. HTTP 503, are retryable.
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::FailedToCreateURLFrom { .. } => false,
+            Self::RequestFailed { status_code, .. } => match status_code {
+                None => true,
+                Some(status_code) => matches!(status_code, 408 | 429 | 500..=599),
+            },
+        }
+    }
+}
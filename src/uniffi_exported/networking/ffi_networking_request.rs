@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 /// An abstraction of a HTTP Network Request to be made FFI Side (Swift side),
 /// e.g. by URLSession in Swift.
@@ -9,4 +10,23 @@ pub struct FFINetworkingRequest {
     pub headers: HashMap<String, String>,
 
     pub body: Vec<u8>,
+
+    /// When Rust side stops awaiting the response, FFI side should use this
+    /// as timeout, e.g. `URLRequest.timeoutInterval`.
+    pub deadline: Option<FFIOperationDeadline>,
+
+    /// The ID Rust side generated for the dispatch of the request, e.g. to
+    /// include in logs FFI side, or as a HTTP header.
+    pub operation_id: Option<u64>,
+}
+
+impl IsCoalescableRequest for FFINetworkingRequest {
+    /// The url, method and a hash of the body.
+    type Key = (String, String, u64);
+
+    fn coalescing_key(&self) -> Option<Self::Key> {
+        let mut hasher = DefaultHasher::new();
+        self.body.hash(&mut hasher);
+        Some((self.url.clone(), self.method.clone(), hasher.finish()))
+    }
 }
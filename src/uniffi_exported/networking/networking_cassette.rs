@@ -0,0 +1,364 @@
+use crate::prelude::*;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A "network antenna" for offline testing, which either records every network
+/// request made by the "network antenna" it wraps, together with its outcome,
+/// to a cassette file, or replays outcomes from a cassette file without
+/// touching the network. Requests are matched on url, method and body.
+///
+/// A cassette file is JSON Lines (`.jsonl`), i.e. one JSON object per line,
+/// one per interaction, rather than a single JSON array, so that recording an
+/// interaction appends a line without rewriting the file, and an interrupted
+/// recording leaves every line recorded before readable.
+///
+/// Replaying serves the interactions recorded for a request in the order they
+/// were recorded, and once all have been served, the last one again and again,
+/// e.g. a request recorded once can be replayed any number of times.
+#[derive(Object)]
+pub struct NetworkingCassette {
+    mode: CassetteMode,
+}
+
+enum CassetteMode {
+    Record {
+        network_antenna: Arc<dyn FFINetworkingExecutor>,
+        recorder: Arc<CassetteRecorder>,
+    },
+    Replay {
+        interactions: Vec<RecordedInteraction>,
+        /// Whether the interaction at the same index has been replayed.
+        replayed: Mutex<Vec<bool>>,
+    },
+}
+
+#[export]
+impl NetworkingCassette {
+    /// A cassette making network requests using `network_antenna`, recording
+    /// every request and its outcome to the cassette file at `cassette_path`,
+    /// appending to it if it already exists.
+    #[uniffi::constructor]
+    pub fn recording(
+        network_antenna: Arc<dyn FFINetworkingExecutor>,
+        cassette_path: String,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            mode: CassetteMode::Record {
+                network_antenna,
+                recorder: Arc::new(CassetteRecorder {
+                    path: cassette_path.into(),
+                    file: Mutex::new(None),
+                }),
+            },
+        })
+    }
+
+    /// A cassette replaying the outcomes recorded in the cassette file at
+    /// `cassette_path`, in the order they were recorded. Once all outcomes of
+    /// a request have been replayed, the last one is replayed again and again.
+    #[uniffi::constructor]
+    pub fn replaying(cassette_path: String) -> Result<Arc<Self>, FFIBridgeError> {
+        let path = PathBuf::from(cassette_path);
+        let interactions =
+            load(&path).map_err(|underlying| RustSideError::FailedToLoadCassette {
+                path: path.display().to_string(),
+                underlying,
+            })?;
+        Ok(Arc::new(Self {
+            mode: CassetteMode::Replay {
+                replayed: Mutex::new(vec![false; interactions.len()]),
+                interactions,
+            },
+        }))
+    }
+
+    /// This cassette as a "network antenna", to construct a [`GatewayClient`] with.
+    pub fn as_network_antenna(self: Arc<Self>) -> Arc<dyn FFINetworkingExecutor> {
+        self
+    }
+}
+
+impl FFINetworkingExecutor for NetworkingCassette {
+    fn execute_networking_request(
+        &self,
+        request: FFINetworkingRequest,
+        listener_rust_side: Arc<FFINetworkingOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFINetworkingOutcome>, FFISideError> {
+        match &self.mode {
+            CassetteMode::Record {
+                network_antenna,
+                recorder,
+            } => {
+                let recorded_request = RecordedRequest::from(&request);
+                let listener = FFIOperationOutcomeListener::forwarding(
+                    FFINetworkingOutcomeListener::OPERATION_KIND,
+                    listener_rust_side.operation_id(),
+                    cancellation_handle.clone(),
+                    {
+                        let recorder = recorder.clone();
+                        let recorded_request = recorded_request.clone();
+                        move |result: Result<FFINetworkingOutcome, RustSideError>| match result {
+                            Ok(outcome) => {
+                                recorder.record(RecordedInteraction {
+                                    request: recorded_request,
+                                    outcome: (&outcome).into(),
+                                });
+                                listener_rust_side.notify_outcome(outcome)
+                            }
+                            Err(error) => listener_rust_side.notify_rust_side_error(error),
+                        }
+                    },
+                );
+                let immediate_outcome = network_antenna.execute_networking_request(
+                    request,
+                    Arc::new(listener.into()),
+                    cancellation_handle,
+                )?;
+                if let Some(outcome) = &immediate_outcome {
+                    recorder.record(RecordedInteraction {
+                        request: recorded_request,
+                        outcome: outcome.into(),
+                    });
+                }
+                Ok(immediate_outcome)
+            }
+            CassetteMode::Replay {
+                interactions,
+                replayed,
+            } => {
+                let request = RecordedRequest::from(&request);
+                let mut replayed = lock_unpoisoned(replayed);
+                let matching = interactions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, interaction)| interaction.request == request)
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                let Some(index) = matching
+                    .iter()
+                    .find(|index| !replayed[**index])
+                    .or(matching.last())
+                    .copied()
+                else {
+                    drop(replayed);
+                    warn!(url = request.url, "No interaction recorded in cassette");
+                    let _ = listener_rust_side.notify_rust_side_error(
+                        RustSideError::NoRecordedInteraction {
+                            method: request.method,
+                            url: request.url,
+                        },
+                    );
+                    return Ok(None);
+                };
+                replayed[index] = true;
+                drop(replayed);
+                debug!(index, url = request.url, "Replaying recorded interaction");
+                Ok(Some(interactions[index].outcome.clone().into()))
+            }
+        }
+    }
+
+    fn networking_capabilities(&self) -> Option<FFINetworkingCapabilities> {
+        match &self.mode {
+            CassetteMode::Record {
+                network_antenna, ..
+            } => network_antenna.networking_capabilities(),
+            CassetteMode::Replay { .. } => None,
+        }
+    }
+}
+
+/// Appends the interactions of a recording cassette to its cassette file.
+struct CassetteRecorder {
+    path: PathBuf,
+    /// Opened on the first recorded interaction, and kept open after.
+    file: Mutex<Option<File>>,
+}
+
+impl CassetteRecorder {
+    /// Appends `interaction` to the cassette file, as a single line, never
+    /// failing the network request it records.
+    fn record(&self, interaction: RecordedInteraction) {
+        let mut line = match serde_json::to_vec(&interaction) {
+            Ok(line) => line,
+            Err(error) => {
+                warn!(%error, "Failed to serialize interaction to record");
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        // Held while writing, so that lines of concurrent requests never interleave.
+        let mut file = lock_unpoisoned(&self.file);
+        if file.is_none() {
+            match OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+            {
+                Ok(opened) => *file = Some(opened),
+                Err(error) => {
+                    warn!(%error, path = %self.path.display(), "Failed to open cassette to record to");
+                    return;
+                }
+            }
+        }
+        if let Some(Err(error)) = file.as_mut().map(|file| file.write_all(&line)) {
+            warn!(%error, path = %self.path.display(), "Failed to record to cassette");
+        }
+    }
+}
+
+/// The interactions recorded in the cassette file at `path`, one per line.
+fn load(path: &Path) -> Result<Vec<RecordedInteraction>, String> {
+    let contents = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(|error| error.to_string()))
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct RecordedInteraction {
+    request: RecordedRequest,
+    outcome: RecordedOutcome,
+}
+
+/// The parts of a [`FFINetworkingRequest`] requests are matched on.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct RecordedRequest {
+    url: String,
+    method: String,
+    body: RecordedBody,
+}
+
+impl From<&FFINetworkingRequest> for RecordedRequest {
+    fn from(value: &FFINetworkingRequest) -> Self {
+        Self {
+            url: value.url.clone(),
+            method: value.method.clone(),
+            body: value.body.clone().into(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+enum RecordedOutcome {
+    Success {
+        status_code: u16,
+        body: RecordedBody,
+    },
+    Failure {
+        error: RecordedError,
+    },
+}
+
+impl From<&FFINetworkingOutcome> for RecordedOutcome {
+    fn from(value: &FFINetworkingOutcome) -> Self {
+        match value {
+            FFINetworkingOutcome::Success { value } => Self::Success {
+                status_code: value.status_code,
+                body: value.body.clone().into(),
+            },
+            FFINetworkingOutcome::Failure { error } => Self::Failure {
+                error: error.into(),
+            },
+        }
+    }
+}
+
+impl From<RecordedOutcome> for FFINetworkingOutcome {
+    fn from(value: RecordedOutcome) -> Self {
+        match value {
+            RecordedOutcome::Success { status_code, body } => Self::Success {
+                value: FFINetworkingResponse {
+                    status_code,
+                    body: body.into(),
+                },
+            },
+            RecordedOutcome::Failure { error } => Self::Failure {
+                error: error.into(),
+            },
+        }
+    }
+}
+
+/// A recorded [`FFINetworkingError`].
+#[derive(Serialize, Deserialize, Clone)]
+enum RecordedError {
+    FailedToCreateURLFrom {
+        string: String,
+    },
+    RequestFailed {
+        status_code: Option<u16>,
+        url_session_underlying_error: Option<String>,
+        error_message_from_gateway: Option<String>,
+    },
+}
+
+impl From<&FFINetworkingError> for RecordedError {
+    fn from(value: &FFINetworkingError) -> Self {
+        match value.clone() {
+            FFINetworkingError::FailedToCreateURLFrom { string } => {
+                Self::FailedToCreateURLFrom { string }
+            }
+            FFINetworkingError::RequestFailed {
+                status_code,
+                url_session_underlying_error,
+                error_message_from_gateway,
+            } => Self::RequestFailed {
+                status_code,
+                url_session_underlying_error,
+                error_message_from_gateway,
+            },
+        }
+    }
+}
+
+impl From<RecordedError> for FFINetworkingError {
+    fn from(value: RecordedError) -> Self {
+        match value {
+            RecordedError::FailedToCreateURLFrom { string } => {
+                Self::FailedToCreateURLFrom { string }
+            }
+            RecordedError::RequestFailed {
+                status_code,
+                url_session_underlying_error,
+                error_message_from_gateway,
+            } => Self::RequestFailed {
+                status_code,
+                url_session_underlying_error,
+                error_message_from_gateway,
+            },
+        }
+    }
+}
+
+/// A body, recorded as text if it is UTF-8, e.g. JSON, so that cassettes can
+/// be read and reviewed, else as bytes.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+enum RecordedBody {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl From<Vec<u8>> for RecordedBody {
+    fn from(value: Vec<u8>) -> Self {
+        String::from_utf8(value)
+            .map(Self::Text)
+            .unwrap_or_else(|error| Self::Bytes(error.into_bytes()))
+    }
+}
+
+impl From<RecordedBody> for Vec<u8> {
+    fn from(value: RecordedBody) -> Self {
+        match value {
+            RecordedBody::Text(text) => text.into_bytes(),
+            RecordedBody::Bytes(bytes) => bytes,
+        }
+    }
+}
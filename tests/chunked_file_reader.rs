@@ -0,0 +1,144 @@
+use ffibre::test_utils::*;
+use ffibre::*;
+use futures_executor::block_on;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+const PATH: &str = "/tmp/ffibre/notes.txt";
+
+/// Notifies chunks of `contents` synchronously, before returning from
+/// `execute_file_io_read_chunks_request`, like a host reading from memory,
+/// until Rust side has no room for more, and then either the remaining chunks
+/// from another thread, awaiting room for each, or only the completion.
+struct SynchronousChunkReader {
+    contents: Vec<u8>,
+    notifies_remaining_chunks: bool,
+    notify_results: Arc<Mutex<Vec<Result<(), NotifyOutcomeError>>>>,
+}
+
+impl SynchronousChunkReader {
+    fn new(contents: Vec<u8>, notifies_remaining_chunks: bool) -> Self {
+        Self {
+            contents,
+            notifies_remaining_chunks,
+            notify_results: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl FFIFileIOReadChunksExecutor for SynchronousChunkReader {
+    fn execute_file_io_read_chunks_request(
+        &self,
+        request: FFIFileIOReadChunksRequest,
+        listener_rust_side: Arc<FFIFileIOReadChunksStreamListener>,
+        _cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<(), FFISideError> {
+        let mut chunks = self
+            .contents
+            .chunks(request.chunk_size as usize)
+            .map(<[u8]>::to_vec)
+            .collect::<VecDeque<_>>();
+        let mut notify_results = self.notify_results.lock().unwrap();
+        while let Some(chunk) = chunks.front() {
+            let result = listener_rust_side.notify_value(chunk.clone());
+            let is_full = result.is_err();
+            notify_results.push(result);
+            if is_full {
+                break;
+            }
+            chunks.pop_front();
+        }
+        if !self.notifies_remaining_chunks {
+            notify_results.push(listener_rust_side.notify_completed());
+            return Ok(());
+        }
+        let notify_results = self.notify_results.clone();
+        std::thread::spawn(move || {
+            for chunk in chunks {
+                let result = block_on(listener_rust_side.notify_value_async(chunk));
+                notify_results.lock().unwrap().push(result);
+            }
+            let result = listener_rust_side.notify_completed();
+            notify_results.lock().unwrap().push(result);
+        });
+        Ok(())
+    }
+}
+
+#[test]
+fn chunks_beyond_the_buffer_size_are_rejected_instead_of_blocking_a_synchronous_host() {
+    // 40 chunks, more than the 16 values Rust side buffers.
+    let contents = (0..40u8).collect::<Vec<_>>();
+    let executor = Arc::new(SynchronousChunkReader::new(contents.clone(), true));
+    let reader = ChunkedFileReader::new(executor.clone());
+
+    let read = reader.read_blocking(PATH.to_owned(), 1);
+
+    assert_eq!(read, Ok(contents));
+    let notify_results = executor.notify_results.lock().unwrap();
+    assert!(notify_results[..16].iter().all(Result::is_ok));
+    assert!(matches!(
+        notify_results[16],
+        Err(NotifyOutcomeError::StreamBufferFull { .. })
+    ));
+    assert_eq!(notify_results.len(), 17 + 24 + 1);
+    assert!(notify_results[17..].iter().all(Result::is_ok));
+}
+
+#[test]
+fn stream_is_completed_even_if_the_buffer_is_full() {
+    let contents = (0..40u8).collect::<Vec<_>>();
+    let executor = Arc::new(SynchronousChunkReader::new(contents.clone(), false));
+    let reader = ChunkedFileReader::new(executor.clone());
+
+    let read = reader.read_blocking(PATH.to_owned(), 1);
+
+    assert_eq!(read, Ok(contents[..16].to_vec()));
+    assert_eq!(
+        executor.notify_results.lock().unwrap().last(),
+        Some(&Ok(()))
+    );
+}
+
+/// Notifies the first chunk of the file, then loses track of the read and
+/// drops the listener without finishing the stream.
+struct ForgetfulChunkReader;
+
+impl FFIFileIOReadChunksExecutor for ForgetfulChunkReader {
+    fn execute_file_io_read_chunks_request(
+        &self,
+        _request: FFIFileIOReadChunksRequest,
+        listener_rust_side: Arc<FFIFileIOReadChunksStreamListener>,
+        _cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<(), FFISideError> {
+        std::thread::spawn(move || {
+            let _ = listener_rust_side.notify_value(vec![1]);
+        });
+        Ok(())
+    }
+}
+
+#[test]
+fn dropped_listener_fails_the_read_and_is_reported_as_host_bug() {
+    let hook = RecordingHostBugHook::install();
+    let reader = ChunkedFileReader::new(Arc::new(ForgetfulChunkReader));
+
+    let result = reader.read_blocking(PATH.to_owned(), 1);
+
+    let Err(FFIBridgeError::FromRust {
+        error: error @ RustSideError::ListenerDroppedWithoutOutcome { .. },
+        operation_id: Some(operation_id),
+    }) = result
+    else {
+        panic!("Expected the read to fail with a dropped listener, got {result:?}");
+    };
+    assert!(matches!(
+        &error,
+        RustSideError::ListenerDroppedWithoutOutcome { operation, operation_id: id, .. }
+            if operation == "file_io_read_chunks" && *id == operation_id
+    ));
+    assert_eq!(reader.stats().never_notified, 1);
+    // The hook is global, so only bugs of this operation are looked at.
+    #[cfg(debug_assertions)]
+    assert_eq!(hook.bugs_of(operation_id), vec![error]);
+}
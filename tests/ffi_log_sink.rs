@@ -0,0 +1,50 @@
+use ffibre::test_utils::*;
+use ffibre::*;
+use std::sync::{Arc, Mutex};
+
+const ADDRESS: &str = "account_rdx16xlfcpp0vf7e3gqnswv8j9k58n6rjccu58vvspmdva22kf3aplease";
+
+#[derive(Default)]
+struct RecordingLogSink(Mutex<Vec<FFILogRecord>>);
+
+impl FFILogSink for RecordingLogSink {
+    fn log(&self, record: FFILogRecord) {
+        self.0.lock().unwrap().push(record)
+    }
+}
+
+// The sink is global, so this binary has a single test installing it.
+#[test]
+fn records_of_at_least_the_min_level_are_logged_with_the_fields_of_their_spans() {
+    let sink = Arc::new(RecordingLogSink::default());
+    install_log_sink(sink.clone(), FFILogLevel::Warn);
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.throw(FFINetworkingError::RequestFailed {
+        status_code: None,
+        url_session_underlying_error: Some("Offline".to_owned()),
+        error_message_from_gateway: None,
+    });
+    let client = GatewayClient::new(executor.clone());
+
+    assert!(client
+        .get_xrd_balance_of_account_blocking(ADDRESS.to_owned())
+        .is_err());
+
+    let operation_id = executor.requests()[0].operation_id.unwrap().to_string();
+    let records = sink.0.lock().unwrap();
+    assert!(records
+        .iter()
+        .all(|record| record.level >= FFILogLevel::Warn));
+    let record = records
+        .iter()
+        .find(|record| record.message == "FFI operation failed")
+        .expect("Failure of the request is logged");
+    assert_eq!(record.level, FFILogLevel::Warn);
+    assert!(record.target.starts_with("ffibre::"));
+    assert_eq!(
+        record.fields.get("operation").map(String::as_str),
+        Some("networking")
+    );
+    assert_eq!(record.fields.get("operation_id"), Some(&operation_id));
+    assert!(record.fields.contains_key("error"));
+}
@@ -0,0 +1,364 @@
+use ffibre::test_utils::*;
+use ffibre::*;
+use futures_executor::block_on;
+use std::sync::{Arc, Mutex};
+
+const PATH: &str = "/tmp/ffibre/notes.txt";
+
+fn exists(contents: &str) -> FFIFileIOReadOutcome {
+    FFIFileIOReadOutcome::Success {
+        value: FFIFileIOReadResponse::Exists {
+            contents: contents.as_bytes().to_vec(),
+        },
+    }
+}
+
+fn did_write() -> FFIFileIOWriteOutcome {
+    FFIFileIOWriteOutcome::Success {
+        value: FFIFileIOWriteResponse::DidWrite {
+            already_existed: true,
+        },
+    }
+}
+
+fn append(separator: &str) -> ExtendExistingFileStrategy {
+    ExtendExistingFileStrategy::Append {
+        separator: separator.to_owned(),
+    }
+}
+
+#[test]
+fn file_is_read() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(exists("Hello"));
+    let interface = FileIOInterface::new(Arc::new(MockFileIOWriteExecutor::new()), reader.clone());
+
+    let served = block_on(interface.read_served(PATH.to_owned())).unwrap();
+
+    assert_eq!(served.contents, Some(b"Hello".to_vec()));
+    assert_eq!(served.served_by, 0);
+    assert_eq!(reader.requests()[0].absolute_path, PATH);
+}
+
+#[test]
+fn file_which_does_not_exist_is_read_as_none() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(FFIFileIOReadOutcome::Success {
+        value: FFIFileIOReadResponse::DoesNotExist,
+    });
+    let interface = FileIOInterface::new(Arc::new(MockFileIOWriteExecutor::new()), reader);
+
+    let served = interface.read_served_blocking(PATH.to_owned()).unwrap();
+
+    assert_eq!(served.contents, None);
+}
+
+#[test]
+fn file_is_extended_by_reading_and_rewriting_it() {
+    let writer = Arc::new(MockFileIOWriteExecutor::new());
+    writer.respond(did_write());
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond_from_another_thread(exists("Hello"));
+    let interface = FileIOInterface::new(writer.clone(), reader.clone());
+
+    let response = interface.write_to_new_or_extend_existing_file_blocking(
+        PATH.to_owned(),
+        append(", "),
+        b"World".to_vec(),
+    );
+
+    assert_eq!(
+        response,
+        Ok(FFIFileIOWriteResponse::DidWrite {
+            already_existed: true
+        })
+    );
+    assert_eq!(reader.requests().len(), 1);
+    let writes = writer.requests();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].contents, b"Hello, World".to_vec());
+    assert_eq!(
+        writes[0].exists_strategy,
+        FileAlreadyExistsStrategy::Overwrite
+    );
+    assert!(!writes[0].atomic);
+}
+
+#[test]
+fn file_is_prepended_to() {
+    let writer = Arc::new(MockFileIOWriteExecutor::new());
+    writer.respond(did_write());
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(exists("World"));
+    let interface = FileIOInterface::new(writer.clone(), reader);
+
+    interface
+        .write_to_new_or_extend_existing_file_blocking(
+            PATH.to_owned(),
+            ExtendExistingFileStrategy::Prepend {
+                separator: ", ".to_owned(),
+            },
+            b"Hello".to_vec(),
+        )
+        .unwrap();
+
+    assert_eq!(writer.requests()[0].contents, b"Hello, World".to_vec());
+}
+
+#[test]
+fn file_is_appended_to_natively_if_supported() {
+    let writer = Arc::new(MockFileIOWriteExecutor::new());
+    writer
+        .set_capabilities(Some(FFIFileIOWriteCapabilities {
+            supports_append: true,
+            supports_atomic_write: true,
+        }))
+        .respond(did_write());
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    let interface = FileIOInterface::new(writer.clone(), reader.clone());
+
+    interface
+        .write_to_new_or_extend_existing_file_blocking(
+            PATH.to_owned(),
+            append("\n"),
+            b"World".to_vec(),
+        )
+        .unwrap();
+
+    assert!(reader.calls().is_empty());
+    let writes = writer.requests();
+    assert_eq!(writes[0].contents, b"World".to_vec());
+    assert_eq!(
+        writes[0].exists_strategy,
+        FileAlreadyExistsStrategy::Append {
+            separator: "\n".to_owned()
+        }
+    );
+}
+
+#[test]
+fn file_is_rewritten_atomically_if_supported() {
+    let writer = Arc::new(MockFileIOWriteExecutor::new());
+    writer
+        .set_capabilities(Some(FFIFileIOWriteCapabilities {
+            supports_append: false,
+            supports_atomic_write: true,
+        }))
+        .respond(did_write());
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(exists("Hello"));
+    let interface = FileIOInterface::new(writer.clone(), reader);
+
+    interface
+        .write_to_new_or_extend_existing_file_blocking(
+            PATH.to_owned(),
+            append(" "),
+            b"World".to_vec(),
+        )
+        .unwrap();
+
+    assert!(writer.requests()[0].atomic);
+}
+
+#[test]
+fn range_is_sliced_unless_read_natively() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(exists("Hello, World"));
+    let interface = FileIOInterface::new(Arc::new(MockFileIOWriteExecutor::new()), reader.clone());
+    let range = FFIByteRange {
+        offset: 7,
+        length: 5,
+    };
+
+    let contents = interface.read_range_blocking(PATH.to_owned(), range);
+
+    assert_eq!(contents, Ok(Some(b"World".to_vec())));
+    assert_eq!(reader.requests()[0].range, None);
+
+    reader.set_capabilities(Some(FFIFileIOReadCapabilities {
+        supports_range_read: true,
+    }));
+    reader.respond(exists("World"));
+
+    let contents = interface.read_range_blocking(PATH.to_owned(), range);
+
+    assert_eq!(contents, Ok(Some(b"World".to_vec())));
+    assert_eq!(reader.requests()[1].range, Some(range));
+}
+
+#[test]
+fn file_is_read_by_fallback_reader_if_reader_fails() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.throw(FFIFileIOReadError::Unknown {
+        underlying: "Disk not mounted".to_owned(),
+    });
+    let fallback_reader = Arc::new(MockFileIOReadExecutor::new());
+    fallback_reader.respond(exists("Bundled"));
+    let interface = FileIOInterface::with_fallback_executors(
+        Arc::new(MockFileIOWriteExecutor::new()),
+        reader.clone(),
+        Vec::new(),
+        vec![fallback_reader.clone()],
+        DispatcherConfiguration {
+            fallback_on: Some(vec![FallbackTrigger::HostFailure]),
+            ..Default::default()
+        },
+    );
+
+    let served = block_on(interface.read_served(PATH.to_owned())).unwrap();
+
+    assert_eq!(served.contents, Some(b"Bundled".to_vec()));
+    assert_eq!(served.served_by, 1);
+    assert_eq!(reader.calls().len(), 1);
+    assert_eq!(fallback_reader.calls().len(), 1);
+}
+
+#[test]
+fn files_are_read_in_order() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(exists("a")).respond(exists("b"));
+    let interface = FileIOInterface::new(Arc::new(MockFileIOWriteExecutor::new()), reader.clone());
+
+    let results = interface
+        .read_files_blocking(vec!["/a".to_owned(), "/b".to_owned()])
+        .unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            FileContentsResult::Success {
+                contents: Some(b"a".to_vec())
+            },
+            FileContentsResult::Success {
+                contents: Some(b"b".to_vec())
+            },
+        ]
+    );
+    // Without a batch executor every file is read on its own.
+    assert_eq!(reader.requests().len(), 2);
+}
+
+/// Reads a batch of files by answering every request with its path as
+/// contents, or with one outcome too few.
+#[derive(Default)]
+struct RecordingBatchReader {
+    drops_last_outcome: bool,
+    batches: Mutex<Vec<Vec<FFIFileIOReadRequest>>>,
+}
+
+impl FFIFileIOReadBatchExecutor for RecordingBatchReader {
+    fn execute_file_io_read_batch_request(
+        &self,
+        requests: Vec<FFIFileIOReadRequest>,
+        listener_rust_side: Arc<FFIFileIOReadBatchOutcomeListener>,
+        _cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<(), FFISideError> {
+        let mut outcomes = requests
+            .iter()
+            .map(|request| exists(&request.absolute_path))
+            .collect::<Vec<_>>();
+        if self.drops_last_outcome {
+            outcomes.pop();
+        }
+        self.batches.lock().unwrap().push(requests);
+        listener_rust_side.notify_outcomes(outcomes).unwrap();
+        Ok(())
+    }
+}
+
+#[test]
+fn files_are_read_in_a_single_batch_if_a_batch_executor_is_set() {
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    let interface = FileIOInterface::new(Arc::new(MockFileIOWriteExecutor::new()), reader.clone());
+    let batch_reader = Arc::new(RecordingBatchReader::default());
+    interface.set_read_batch_executor(batch_reader.clone());
+
+    let results = interface
+        .read_files_blocking(vec!["/a".to_owned(), "/b".to_owned()])
+        .unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            FileContentsResult::Success {
+                contents: Some(b"/a".to_vec())
+            },
+            FileContentsResult::Success {
+                contents: Some(b"/b".to_vec())
+            },
+        ]
+    );
+    assert!(reader.requests().is_empty());
+    let batches = batch_reader.batches.lock().unwrap();
+    assert_eq!(batches.len(), 1);
+    let operation_ids = batches[0]
+        .iter()
+        .map(|request| request.operation_id.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(operation_ids.len(), 2);
+    assert_ne!(operation_ids[0], operation_ids[1]);
+    assert_eq!(interface.read_stats().dispatched, 2);
+}
+
+#[test]
+fn batch_with_an_outcome_missing_fails_every_read_of_it() {
+    let interface = FileIOInterface::new(
+        Arc::new(MockFileIOWriteExecutor::new()),
+        Arc::new(MockFileIOReadExecutor::new()),
+    );
+    interface.set_read_batch_executor(Arc::new(RecordingBatchReader {
+        drops_last_outcome: true,
+        ..Default::default()
+    }));
+
+    let results = interface
+        .read_files_blocking(vec!["/a".to_owned(), "/b".to_owned()])
+        .unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| matches!(
+        result,
+        FileContentsResult::Failure {
+            error: FFIBridgeError::FromRust {
+                error: RustSideError::BatchOutcomeCountMismatch {
+                    expected: 2,
+                    actual: 1,
+                    ..
+                },
+                operation_id: Some(_),
+            }
+        }
+    )));
+}
+
+#[test]
+fn progress_of_a_write_is_observed() {
+    let progress = [
+        FFIOperationProgress {
+            completed: 0,
+            total: Some(5),
+        },
+        FFIOperationProgress {
+            completed: 5,
+            total: Some(5),
+        },
+    ];
+    let writer = Arc::new(MockFileIOWriteExecutor::new());
+    writer.then(MockStep::new(MockBehaviour::Notify(did_write())).with_progress(progress));
+    let reader = Arc::new(MockFileIOReadExecutor::new());
+    reader.respond(FFIFileIOReadOutcome::Success {
+        value: FFIFileIOReadResponse::DoesNotExist,
+    });
+    let interface = FileIOInterface::new(writer, reader);
+    let observer = Arc::new(RecordingProgressObserver::new());
+
+    let response = interface.write_to_new_or_extend_existing_file_with_progress_blocking(
+        PATH.to_owned(),
+        append(", "),
+        b"Hello".to_vec(),
+        observer.clone(),
+    );
+
+    assert!(response.is_ok());
+    assert_eq!(observer.progress(), progress);
+}
@@ -0,0 +1,733 @@
+use ffibre::test_utils::*;
+use ffibre::*;
+use futures_executor::block_on;
+use futures_util::future::{join, join3, join4};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const ADDRESS: &str = "account_rdx16xlfcpp0vf7e3gqnswv8j9k58n6rjccu58vvspmdva22kf3aplease";
+
+fn request_failed(status_code: u16) -> FFINetworkingOutcome {
+    FFINetworkingOutcome::Failure {
+        error: FFINetworkingError::RequestFailed {
+            status_code: Some(status_code),
+            url_session_underlying_error: None,
+            error_message_from_gateway: None,
+        },
+    }
+}
+
+fn rust_side_error<T: std::fmt::Debug>(result: Result<T, FFIBridgeError>) -> RustSideError {
+    match result {
+        Err(FFIBridgeError::FromRust { error, .. }) => error,
+        result => panic!("Expected a Rust side error, got {result:?}"),
+    }
+}
+
+#[test]
+fn balance_is_parsed_from_the_response() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(balance_response("1337"));
+    let client = GatewayClient::new(executor.clone());
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1337".to_owned()));
+    let requests = executor.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].url,
+        "https://mainnet.radixdlt.com/state/entity/details"
+    );
+    assert_eq!(requests[0].method, "POST");
+    assert!(String::from_utf8(requests[0].body.clone())
+        .unwrap()
+        .contains(ADDRESS));
+    assert!(requests[0].operation_id.is_some());
+}
+
+#[test]
+fn served_balance_tells_which_executor_made_the_request() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(balance_response("42"));
+    let client = GatewayClient::new(executor);
+
+    let served = client.get_xrd_balance_of_account_served_blocking(ADDRESS.to_owned());
+
+    assert_eq!(
+        served,
+        Ok(ServedXrdBalance {
+            balance: "42".to_owned(),
+            served_by: 0,
+        })
+    );
+}
+
+#[test]
+fn failure_from_the_executor_is_returned() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(request_failed(404));
+    let client = GatewayClient::new(executor);
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        result,
+        Err(FFIBridgeError::FromFFI {
+            error: FFISideError::Networking {
+                error: FFINetworkingError::RequestFailed {
+                    status_code: Some(404),
+                    ..
+                }
+            },
+            operation_id: Some(_),
+        })
+    ));
+}
+
+#[test]
+fn executor_throwing_fails_the_request() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.throw(FFINetworkingError::FailedToCreateURLFrom {
+        string: "not a url".to_owned(),
+    });
+    let client = GatewayClient::new(executor);
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(result, Err(FFIBridgeError::FromFFI { .. })));
+}
+
+#[test]
+fn outcome_notified_from_another_thread_after_a_delay_is_awaited() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond_after(Duration::from_millis(20), balance_response("1"));
+    let client = GatewayClient::new(executor);
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1".to_owned()));
+}
+
+#[test]
+fn never_notified_request_times_out_and_is_cancelled() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let client = GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            timeout_ms: Some(20),
+            ..Default::default()
+        },
+    );
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        rust_side_error(result),
+        RustSideError::OperationTimedOut { .. }
+    ));
+    assert!(executor.calls()[0].cancellation_handle.is_cancelled());
+}
+
+#[test]
+fn timeout_of_a_single_call_caps_the_call() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let client = GatewayClient::new(executor.clone());
+
+    let result = client.get_xrd_balance_of_account_with_options_blocking(
+        ADDRESS.to_owned(),
+        OperationOptions {
+            timeout_ms: Some(20),
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(
+        rust_side_error(result),
+        RustSideError::OperationTimedOut { .. }
+    ));
+    assert!(executor.requests()[0].deadline.is_some());
+}
+
+#[test]
+fn timeout_of_the_configuration_applies_to_every_attempt_of_a_call_with_a_timeout() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let client = GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            timeout_ms: Some(20),
+            ..Default::default()
+        },
+    );
+
+    let result = client.get_xrd_balance_of_account_served_with_options_blocking(
+        ADDRESS.to_owned(),
+        OperationOptions {
+            timeout_ms: Some(60_000),
+            ..Default::default()
+        },
+    );
+
+    assert!(matches!(
+        rust_side_error(result),
+        RustSideError::OperationTimedOut { elapsed_ms, .. } if elapsed_ms < 10_000
+    ));
+    assert!(executor.calls()[0].cancellation_handle.is_cancelled());
+}
+
+#[test]
+fn dropped_listener_fails_the_request_and_is_reported_as_host_bug() {
+    let hook = RecordingHostBugHook::install();
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.drop_listener();
+    let client = GatewayClient::new(executor.clone());
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    let error = rust_side_error(result);
+    let operation_id = executor.requests()[0].operation_id.unwrap();
+    assert!(matches!(
+        &error,
+        RustSideError::ListenerDroppedWithoutOutcome { operation, operation_id: id, .. }
+            if operation == "networking" && *id == operation_id
+    ));
+    assert_eq!(client.stats().never_notified, 1);
+    // The hook is global, so only bugs of this operation are looked at.
+    #[cfg(debug_assertions)]
+    assert_eq!(hook.bugs_of(operation_id), vec![error]);
+}
+
+#[test]
+fn notifying_twice_is_rejected() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond_twice(balance_response("1"));
+    let client = GatewayClient::new(executor.clone());
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1".to_owned()));
+    let notify_results = executor.notify_results();
+    assert_eq!(notify_results.len(), 2);
+    assert!(notify_results[0].is_ok());
+    assert!(matches!(
+        notify_results[1],
+        Err(NotifyOutcomeError::AlreadyNotified { .. })
+    ));
+}
+
+#[test]
+fn retryable_failures_are_retried() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor
+        .respond(request_failed(503))
+        .respond_from_another_thread(balance_response("2"));
+    let client = GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            retry_policy: Some(RetryPolicy {
+                initial_backoff_ms: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("2".to_owned()));
+    let requests = executor.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].operation_id, requests[1].operation_id);
+}
+
+#[test]
+fn request_unsupported_by_the_executor_is_never_made() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.set_capabilities(Some(FFINetworkingCapabilities {
+        supported_methods: vec!["GET".to_owned()],
+        max_body_size: None,
+    }));
+    let client = GatewayClient::new(executor.clone());
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        rust_side_error(result),
+        RustSideError::UnsupportedByExecutor { .. }
+    ));
+    assert!(executor.calls().is_empty());
+}
+
+#[test]
+fn request_unsupported_by_the_executor_fails_with_an_operation_id() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.set_capabilities(Some(FFINetworkingCapabilities {
+        supported_methods: vec!["GET".to_owned()],
+        max_body_size: None,
+    }));
+    let client = GatewayClient::new(executor);
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        result,
+        Err(FFIBridgeError::FromRust {
+            error: RustSideError::UnsupportedByExecutor { .. },
+            operation_id: Some(_),
+        })
+    ));
+}
+
+#[test]
+fn response_which_cannot_be_parsed_fails_with_the_operation_id_of_the_request() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(FFINetworkingOutcome::Success {
+        value: FFINetworkingResponse {
+            status_code: 200,
+            body: b"not json".to_vec(),
+        },
+    });
+    let client = GatewayClient::new(executor.clone());
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    let Err(FFIBridgeError::FromRust {
+        error: RustSideError::UnableJSONDeserializeHTTPResponseBodyIntoTypeName { .. },
+        operation_id,
+    }) = result
+    else {
+        panic!("Expected the response to fail to parse, got {result:?}");
+    };
+    assert!(operation_id.is_some());
+    assert_eq!(operation_id, executor.requests()[0].operation_id);
+}
+
+#[test]
+fn response_without_balance_fails_with_the_operation_id_of_the_request() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(FFINetworkingOutcome::Success {
+        value: FFINetworkingResponse {
+            status_code: 200,
+            body: br#"{"items":[{"fungible_resources":{"items":[]}}]}"#.to_vec(),
+        },
+    });
+    let client = GatewayClient::new(executor.clone());
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(
+        rust_side_error(result.clone()),
+        RustSideError::NoXRDBalanceFound
+    );
+    assert_eq!(
+        result.unwrap_err().operation_id(),
+        executor.requests()[0].operation_id
+    );
+}
+
+#[test]
+fn balances_of_many_accounts_keep_their_order() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor
+        .respond_after(Duration::from_millis(30), balance_response("1"))
+        .respond_after(Duration::from_millis(10), balance_response("2"));
+    let client = GatewayClient::new(executor);
+
+    let balances =
+        block_on(client.get_xrd_balances_of_accounts(vec![ADDRESS.to_owned(), ADDRESS.to_owned()]));
+
+    assert_eq!(balances, Ok(vec!["1".to_owned(), "2".to_owned()]));
+}
+
+#[test]
+fn progress_of_the_download_of_transactions_is_observed() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    let progress = [
+        FFIOperationProgress {
+            completed: 0,
+            total: None,
+        },
+        FFIOperationProgress {
+            completed: 11,
+            total: Some(11),
+        },
+    ];
+    executor.then(
+        MockStep::new(MockBehaviour::Notify(FFINetworkingOutcome::Success {
+            value: FFINetworkingResponse {
+                status_code: 200,
+                body: br#"{"items":[]}"#.to_vec(),
+            },
+        }))
+        .with_progress(progress),
+    );
+    let client = GatewayClient::new(executor);
+    let observer = Arc::new(RecordingProgressObserver::new());
+
+    let transactions = client.get_latest_transactions_with_progress_blocking(observer.clone());
+
+    assert_eq!(transactions, Ok(Vec::new()));
+    assert_eq!(observer.progress(), progress);
+}
+
+#[test]
+fn immediately_returned_outcome_is_used() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond_immediately(balance_response("3"));
+    let client = GatewayClient::new(executor.clone());
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("3".to_owned()));
+    assert!(executor.notify_results().is_empty());
+}
+
+#[test]
+fn returned_outcome_wins_over_outcome_notified_before_returning() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.then(MockStep::new(MockBehaviour::ReturnAndNotify {
+        returned: balance_response("1"),
+        notified: balance_response("2"),
+    }));
+    let client = GatewayClient::new(executor);
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1".to_owned()));
+}
+
+#[test]
+fn notifying_after_returning_outcome_is_rejected() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.then(MockStep::new(MockBehaviour::ReturnAndKeepListener(
+        balance_response("1"),
+    )));
+    let client = GatewayClient::new(executor.clone());
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+    executor.notify_pending(balance_response("2"));
+
+    assert_eq!(balance, Ok("1".to_owned()));
+    assert!(matches!(
+        executor.notify_results()[..],
+        [Err(NotifyOutcomeError::AlreadyNotified { .. })]
+    ));
+}
+
+#[test]
+fn queued_call_with_higher_priority_is_made_first() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor
+        .never_respond()
+        .respond(balance_response("2"))
+        .respond(balance_response("3"));
+    let client = GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            max_in_flight: Some(1),
+            ..Default::default()
+        },
+    );
+    let call_with_priority = |address: &str, priority: OperationPriority| {
+        client.get_xrd_balance_of_account_with_options(
+            address.to_owned(),
+            OperationOptions {
+                priority: Some(priority),
+                ..Default::default()
+            },
+        )
+    };
+
+    // Polled in order, so the calls are queued behind the one in flight
+    // before it finishes.
+    let (in_flight, low, high, ()) = block_on(join4(
+        call_with_priority("account_in_flight", OperationPriority::Normal),
+        call_with_priority("account_low", OperationPriority::Low),
+        call_with_priority("account_high", OperationPriority::High),
+        async {
+            assert_eq!(client.queue_depth(), 2);
+            executor.notify_pending(balance_response("1"))
+        },
+    ));
+
+    assert_eq!(in_flight, Ok("1".to_owned()));
+    assert_eq!(high, Ok("2".to_owned()));
+    assert_eq!(low, Ok("3".to_owned()));
+    let addresses = executor
+        .requests()
+        .into_iter()
+        .map(|request| {
+            let body = String::from_utf8(request.body).unwrap();
+            ["account_in_flight", "account_low", "account_high"]
+                .into_iter()
+                .find(|address| body.contains(address))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        addresses,
+        vec!["account_in_flight", "account_high", "account_low"]
+    );
+}
+
+fn coalescing_client(executor: &Arc<MockNetworkingExecutor>) -> GatewayClient {
+    GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            coalesce_requests: true,
+            ..Default::default()
+        },
+    )
+}
+
+fn with_timeout_ms(timeout_ms: u64) -> OperationOptions {
+    OperationOptions {
+        timeout_ms: Some(timeout_ms),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn coalesced_call_shares_the_outcome_notified_for_the_call_in_flight() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let client = coalescing_client(&executor);
+
+    // Polled in order, so the first call is in flight when the second one is made.
+    let (first, second, ()) = block_on(join3(
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        async { executor.notify_pending(balance_response("1")) },
+    ));
+
+    assert_eq!(first, Ok("1".to_owned()));
+    assert_eq!(second, Ok("1".to_owned()));
+    assert_eq!(executor.requests().len(), 1);
+}
+
+#[test]
+fn coalesced_call_is_dispatched_again_if_the_call_in_flight_times_out() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond().respond(balance_response("2"));
+    let client = coalescing_client(&executor);
+
+    let (first, second) = block_on(join(
+        client.get_xrd_balance_of_account_with_options(ADDRESS.to_owned(), with_timeout_ms(10)),
+        client.get_xrd_balance_of_account_with_options(ADDRESS.to_owned(), with_timeout_ms(60_000)),
+    ));
+
+    assert!(matches!(
+        rust_side_error(first),
+        RustSideError::OperationTimedOut { .. }
+    ));
+    assert_eq!(second, Ok("2".to_owned()));
+    let requests = executor.requests();
+    assert_eq!(requests.len(), 2);
+    assert_ne!(requests[0].operation_id, requests[1].operation_id);
+}
+
+#[test]
+fn coalesced_call_is_dispatched_again_if_the_listener_of_the_call_in_flight_is_dropped() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond().respond(balance_response("3"));
+    let client = coalescing_client(&executor);
+
+    let (first, second, ()) = block_on(join3(
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        async { executor.drop_pending() },
+    ));
+
+    assert!(matches!(
+        rust_side_error(first),
+        RustSideError::ListenerDroppedWithoutOutcome { .. }
+    ));
+    assert_eq!(second, Ok("3".to_owned()));
+    assert_eq!(executor.requests().len(), 2);
+}
+
+#[test]
+fn coalesced_call_times_out_at_its_own_deadline_with_its_own_operation_id() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let client = coalescing_client(&executor);
+
+    let (first, second) = block_on(join(
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        async {
+            let second = client
+                .get_xrd_balance_of_account_with_options(ADDRESS.to_owned(), with_timeout_ms(10))
+                .await;
+            executor.notify_pending(balance_response("4"));
+            second
+        },
+    ));
+
+    assert_eq!(first, Ok("4".to_owned()));
+    let Err(FFIBridgeError::FromRust {
+        error: RustSideError::OperationTimedOut { .. },
+        operation_id: Some(operation_id),
+    }) = second
+    else {
+        panic!("Expected the coalesced call to time out, got {second:?}");
+    };
+    assert_ne!(Some(operation_id), executor.requests()[0].operation_id);
+    assert_eq!(executor.requests().len(), 1);
+    assert_eq!(client.stats().timed_out, 1);
+}
+
+fn circuit_breaking_client(
+    executor: &Arc<MockNetworkingExecutor>,
+    open_duration_ms: u64,
+) -> GatewayClient {
+    GatewayClient::with_configuration(
+        executor.clone(),
+        DispatcherConfiguration {
+            circuit_breaker: Some(CircuitBreakerPolicy {
+                failure_rate_threshold: 0.5,
+                window_size: 2,
+                minimum_operations: 2,
+                open_duration_ms,
+                half_open_trial_operations: 1,
+            }),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn circuit_opens_once_too_many_requests_failed_and_fails_fast() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(request_failed(503));
+    let client = circuit_breaking_client(&executor, 60_000);
+
+    for _ in 0..2 {
+        assert!(client
+            .get_xrd_balance_of_account_blocking(ADDRESS.to_owned())
+            .is_err());
+    }
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        rust_side_error(result),
+        RustSideError::CircuitOpen { operation, retry_after_ms }
+            if operation == "networking" && retry_after_ms > 0
+    ));
+    assert_eq!(client.circuit_state(), CircuitState::Open);
+    assert_eq!(executor.requests().len(), 2);
+}
+
+#[test]
+fn circuit_closes_once_the_trial_request_succeeds() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor
+        .respond(request_failed(503))
+        .respond(request_failed(503))
+        .respond(balance_response("1"));
+    // Half-open as soon as it opens.
+    let client = circuit_breaking_client(&executor, 0);
+    for _ in 0..2 {
+        assert!(client
+            .get_xrd_balance_of_account_blocking(ADDRESS.to_owned())
+            .is_err());
+    }
+    assert_eq!(client.circuit_state(), CircuitState::HalfOpen);
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1".to_owned()));
+    assert_eq!(client.circuit_state(), CircuitState::Closed);
+    assert_eq!(executor.requests().len(), 3);
+}
+
+/// Adds a header to every request and records every outcome.
+#[derive(Default)]
+struct RecordingInterceptor {
+    outcomes: Mutex<Vec<FFINetworkingOutcome>>,
+}
+
+impl FFINetworkingInterceptor for RecordingInterceptor {
+    fn intercept_networking_request(
+        &self,
+        mut request: FFINetworkingRequest,
+    ) -> FFINetworkingRequest {
+        request
+            .headers
+            .insert("Authorization".to_owned(), "Bearer token".to_owned());
+        request
+    }
+
+    fn intercept_networking_outcome(&self, outcome: FFINetworkingOutcome) {
+        self.outcomes.lock().unwrap().push(outcome)
+    }
+}
+
+#[test]
+fn interceptors_modify_every_request_and_inspect_every_outcome() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(balance_response("1"));
+    let client = GatewayClient::new(executor.clone());
+    let interceptor = Arc::new(RecordingInterceptor::default());
+    client.add_interceptor(interceptor.clone());
+
+    let balance = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1".to_owned()));
+    let request = &executor.requests()[0];
+    assert_eq!(
+        request.headers.get("Authorization").map(String::as_str),
+        Some("Bearer token")
+    );
+    assert_eq!(
+        request.headers.get("Content-Type").map(String::as_str),
+        Some("application/json")
+    );
+    assert!(matches!(
+        interceptor.outcomes.lock().unwrap()[..],
+        [FFINetworkingOutcome::Success { .. }]
+    ));
+}
+
+#[test]
+fn requests_made_after_replacing_the_executor_are_made_by_the_new_one() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(balance_response("1"));
+    let replacement = Arc::new(MockNetworkingExecutor::new());
+    replacement.respond(balance_response("2"));
+    let client = GatewayClient::new(executor.clone());
+    assert_eq!(
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        Ok("1".to_owned())
+    );
+
+    client.replace_executor(replacement.clone());
+
+    assert_eq!(
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        Ok("2".to_owned())
+    );
+    assert_eq!(executor.requests().len(), 1);
+    assert_eq!(replacement.requests().len(), 1);
+}
+
+#[test]
+fn request_in_flight_finishes_on_the_replaced_executor() {
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.never_respond();
+    let replacement = Arc::new(MockNetworkingExecutor::new());
+    let client = GatewayClient::new(executor.clone());
+
+    let (balance, ()) = block_on(join(
+        client.get_xrd_balance_of_account(ADDRESS.to_owned()),
+        async {
+            client.replace_executor(replacement.clone());
+            executor.notify_pending(balance_response("1"))
+        },
+    ));
+
+    assert_eq!(balance, Ok("1".to_owned()));
+    assert!(replacement.requests().is_empty());
+}
@@ -0,0 +1,243 @@
+use ffibre::native_executors::*;
+use ffibre::*;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn file_io_interface() -> FileIOInterface {
+    let executor = Arc::new(NativeFileIOExecutor::new());
+    FileIOInterface::new(executor.clone(), executor)
+}
+
+/// A path in a directory of its own, which does not exist yet.
+fn temporary_path(test: &str) -> String {
+    let directory: PathBuf = std::env::temp_dir()
+        .join("ffibre-native-executors")
+        .join(format!("{test}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory.join("notes.txt").display().to_string()
+}
+
+fn append(separator: &str) -> ExtendExistingFileStrategy {
+    ExtendExistingFileStrategy::Append {
+        separator: separator.to_owned(),
+    }
+}
+
+#[test]
+fn file_is_created_and_then_appended_to() {
+    let interface = file_io_interface();
+    let path = temporary_path("append");
+
+    let created = interface.write_to_new_or_extend_existing_file_blocking(
+        path.clone(),
+        append("\n"),
+        b"Hello".to_vec(),
+    );
+    let appended = interface.write_to_new_or_extend_existing_file_blocking(
+        path.clone(),
+        append("\n"),
+        b"World".to_vec(),
+    );
+
+    assert_eq!(
+        created,
+        Ok(FFIFileIOWriteResponse::DidWrite {
+            already_existed: false
+        })
+    );
+    assert_eq!(
+        appended,
+        Ok(FFIFileIOWriteResponse::DidWrite {
+            already_existed: true
+        })
+    );
+    assert_eq!(std::fs::read(&path).unwrap(), b"Hello\nWorld".to_vec());
+}
+
+#[test]
+fn file_is_prepended_to_atomically() {
+    let interface = file_io_interface();
+    let path = temporary_path("prepend");
+    std::fs::write(&path, "World").unwrap();
+
+    interface
+        .write_to_new_or_extend_existing_file_blocking(
+            path.clone(),
+            ExtendExistingFileStrategy::Prepend {
+                separator: ", ".to_owned(),
+            },
+            b"Hello".to_vec(),
+        )
+        .unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World".to_vec());
+    let directory = std::path::Path::new(&path).parent().unwrap();
+    assert_eq!(std::fs::read_dir(directory).unwrap().count(), 1);
+}
+
+#[test]
+fn file_and_range_of_file_are_read() {
+    let interface = file_io_interface();
+    let path = temporary_path("read");
+    std::fs::write(&path, "Hello, World").unwrap();
+
+    let served = futures_executor::block_on(interface.read_served(path.clone())).unwrap();
+    let range = interface.read_range_blocking(
+        path,
+        FFIByteRange {
+            offset: 7,
+            length: 100,
+        },
+    );
+
+    assert_eq!(served.contents, Some(b"Hello, World".to_vec()));
+    assert_eq!(range, Ok(Some(b"World".to_vec())));
+}
+
+#[test]
+fn file_which_does_not_exist_is_read_as_none() {
+    let interface = file_io_interface();
+    let path = temporary_path("missing");
+
+    let files = interface.read_files_blocking(vec![path]).unwrap();
+
+    assert_eq!(files, vec![FileContentsResult::Success { contents: None }]);
+}
+
+const ADDRESS: &str = "account_rdx16xlfcpp0vf7e3gqnswv8j9k58n6rjccu58vvspmdva22kf3aplease";
+
+/// Makes the requests of a [`GatewayClient`] to `base_url` instead of mainnet,
+/// using a [`NativeNetworkingExecutor`].
+struct RedirectingNetworkAntenna {
+    base_url: String,
+    executor: NativeNetworkingExecutor,
+}
+
+impl FFINetworkingExecutor for RedirectingNetworkAntenna {
+    fn execute_networking_request(
+        &self,
+        mut request: FFINetworkingRequest,
+        listener_rust_side: Arc<FFINetworkingOutcomeListener>,
+        cancellation_handle: Arc<FFICancellationHandle>,
+    ) -> Result<Option<FFINetworkingOutcome>, FFISideError> {
+        request.url = request
+            .url
+            .replace("https://mainnet.radixdlt.com", &self.base_url);
+        self.executor
+            .execute_networking_request(request, listener_rust_side, cancellation_handle)
+    }
+
+    fn networking_capabilities(&self) -> Option<FFINetworkingCapabilities> {
+        self.executor.networking_capabilities()
+    }
+}
+
+fn gateway_client(base_url: String) -> GatewayClient {
+    GatewayClient::new(Arc::new(RedirectingNetworkAntenna {
+        base_url,
+        executor: NativeNetworkingExecutor::new(),
+    }))
+}
+
+/// Serves a single HTTP request on a local port, responding with `status` and
+/// `body`, returning the base url of the server.
+fn serve_once(status: &str, body: &str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        read_request(&mut stream);
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+    base_url
+}
+
+/// Reads the head and the body of a HTTP request from `stream`.
+fn read_request(stream: &mut TcpStream) {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    loop {
+        let read = stream.read(&mut buffer).unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let request = String::from_utf8_lossy(&request);
+        let Some(head_length) = request.find("\r\n\r\n") else {
+            continue;
+        };
+        let content_length = request[..head_length]
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("content-length")
+                    .then(|| value.trim().parse::<usize>().unwrap())
+            })
+            .unwrap_or(0);
+        if read == 0 || request.len() >= head_length + 4 + content_length {
+            return;
+        }
+    }
+}
+
+#[test]
+fn balance_is_read_over_http() {
+    let base_url = serve_once(
+        "200 OK",
+        r#"{"items":[{"fungible_resources":{"items":[{"amount":"1337","resource_address":"resource_rdx1tknxxxxxxxxxradxrdxxxxxxxxx009923554798xxxxxxxxxradxrd"}]}}]}"#,
+    );
+
+    let balance = gateway_client(base_url).get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(balance, Ok("1337".to_owned()));
+}
+
+#[test]
+fn non_2xx_response_fails_the_request() {
+    let base_url = serve_once("404 Not Found", r#"{"message":"Entity not found"}"#);
+
+    let result = gateway_client(base_url).get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert_eq!(
+        result.map_err(|error| match error {
+            FFIBridgeError::FromFFI {
+                error: FFISideError::Networking { error },
+                ..
+            } => Some(error),
+            _ => None,
+        }),
+        Err(Some(FFINetworkingError::RequestFailed {
+            status_code: Some(404),
+            url_session_underlying_error: None,
+            error_message_from_gateway: Some(r#"{"message":"Entity not found"}"#.to_owned()),
+        }))
+    );
+}
+
+#[test]
+fn connection_failure_fails_the_request_without_status_code() {
+    // Nothing listens on the port once the listener is dropped.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let result = gateway_client(base_url).get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        result,
+        Err(FFIBridgeError::FromFFI {
+            error: FFISideError::Networking {
+                error: FFINetworkingError::RequestFailed {
+                    status_code: None,
+                    url_session_underlying_error: Some(_),
+                    ..
+                }
+            },
+            ..
+        })
+    ));
+}
@@ -0,0 +1,80 @@
+use ffibre::test_utils::*;
+use ffibre::*;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const ADDRESS: &str = "account_rdx16xlfcpp0vf7e3gqnswv8j9k58n6rjccu58vvspmdva22kf3aplease";
+
+fn cassette_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ffibre-{}-{name}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn recorded_interactions_are_replayed() {
+    let path = cassette_path("recorded");
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor
+        .respond(balance_response("1"))
+        .respond_immediately(balance_response("2"));
+    let recording = NetworkingCassette::recording(executor, path.display().to_string());
+    let client = GatewayClient::new(recording.as_network_antenna());
+    let recorded = [
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+    ];
+
+    let replaying = NetworkingCassette::replaying(path.display().to_string()).unwrap();
+    let client = GatewayClient::new(replaying.as_network_antenna());
+    let replayed = [
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+    ];
+
+    assert_eq!(recorded, [Ok("1".to_owned()), Ok("2".to_owned())]);
+    assert_eq!(replayed, recorded);
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn last_recorded_interaction_of_a_request_is_replayed_again_and_again() {
+    let path = cassette_path("replayed_again");
+    let executor = Arc::new(MockNetworkingExecutor::new());
+    executor.respond(balance_response("1"));
+    let recording = NetworkingCassette::recording(executor, path.display().to_string());
+    let recorded = GatewayClient::new(recording.as_network_antenna())
+        .get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    let replaying = NetworkingCassette::replaying(path.display().to_string()).unwrap();
+    let client = GatewayClient::new(replaying.as_network_antenna());
+    let replayed = [
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+        client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned()),
+    ];
+
+    assert_eq!(recorded, Ok("1".to_owned()));
+    assert_eq!(replayed, [recorded.clone(), recorded.clone(), recorded]);
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn request_never_recorded_fails_rust_side() {
+    let path = cassette_path("empty");
+    std::fs::write(&path, "").unwrap();
+    let replaying = NetworkingCassette::replaying(path.display().to_string()).unwrap();
+    let client = GatewayClient::new(replaying.as_network_antenna());
+
+    let result = client.get_xrd_balance_of_account_blocking(ADDRESS.to_owned());
+
+    assert!(matches!(
+        result,
+        Err(FFIBridgeError::FromRust {
+            error: RustSideError::NoRecordedInteraction { .. },
+            operation_id: Some(_),
+        })
+    ));
+    let _ = std::fs::remove_file(path);
+}
@@ -8,7 +8,13 @@ import okhttp3.Headers.Companion.toHeaders
 object KotlinNetworkAntenna: FfiNetworkingExecutor {
     private val client = OkHttpClient()
 
-    override fun executeNetworkingRequest(request: FfiNetworkingRequest, listenerRustSide: FfiNetworkingOutcomeListener) {
+    override fun networkingCapabilities(): FfiNetworkingCapabilities? = null
+
+    override fun executeNetworkingRequest(
+        request: FfiNetworkingRequest,
+        listenerRustSide: FfiNetworkingOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiNetworkingOutcome? {
         val outcome = runCatching {
             val contentType = request.headers["Content-Type"] ?: "application/json; charset=utf-8"
 
@@ -54,11 +60,23 @@ object KotlinNetworkAntenna: FfiNetworkingExecutor {
         )
 
         listenerRustSide.notifyOutcome(result = outcome)
+        return null
+    }
+}
+
+// Replays the network requests recorded in the cassette, unless `FFIBRE_RECORD_CASSETTES` is set,
+// in which case `networkAntenna` makes them against mainnet and they are recorded.
+fun cassette(networkAntenna: FfiNetworkingExecutor): FfiNetworkingExecutor {
+    val path = "${System.getenv("CARGO_MANIFEST_DIR")}/tests/cassettes/latest_transactions.jsonl"
+    return if (System.getenv("FFIBRE_RECORD_CASSETTES") != null) {
+        NetworkingCassette.recording(networkAntenna = networkAntenna, cassettePath = path).asNetworkAntenna()
+    } else {
+        NetworkingCassette.replaying(cassettePath = path).asNetworkAntenna()
     }
 }
 
 fun testAsyncStream(): Flow<Transaction> = flow {
-    val client = GatewayClient(networkAntenna = KotlinNetworkAntenna)
+    val client = GatewayClient(networkAntenna = cassette(KotlinNetworkAntenna))
 
     while (true) {
         val transaction = client.getLatestTransactionsOrPanic()
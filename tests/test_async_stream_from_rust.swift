@@ -94,10 +94,16 @@ extension FfiNetworkingRequest {
 
 // Conform `[Swift]URLSession` to `[Rust]FfiNetworkingExecutor`
 extension URLSession: FfiNetworkingExecutor {
+  public func networkingCapabilities() -> FfiNetworkingCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeNetworkingRequest(
     request rustRequest: FfiNetworkingRequest,
-    listenerRustSide: FfiNetworkingOutcomeListener
-  ) throws {
+    listenerRustSide: FfiNetworkingOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiNetworkingOutcome? {
     guard let url = URL(string: rustRequest.url) else {
       throw FfiNetworkingError.failedToCreateUrlFrom(string: rustRequest.url)
     }
@@ -107,16 +113,17 @@ extension URLSession: FfiNetworkingExecutor {
         urlResponse: urlResponse,
         error: error
       )
-      listenerRustSide.notifyOutcome(result: result)
+      try? listenerRustSide.notifyOutcome(result: result)
     }
     task.resume()
+    return nil
   }
 }
 
 final class AsyncSubject<T> {
   private let continuation: AsyncStream<T>.Continuation
   private let stream: AsyncStream<T>
-  private var rustSideCancellationListener: CancellationListener?
+  private var rustSideCancellationHandle: FfiCancellationHandle?
   private init() {
     let (stream, continuation) = AsyncStream<T>.makeStream()
     self.stream = stream
@@ -133,7 +140,7 @@ final class AsyncSubject<T> {
     subject.continuation.onTermination = { termination in
       print("❌ SWIFT subject.continuation.onTermination: \(termination)")
       task.cancel()
-      subject.rustSideCancellationListener?.notifyCancelled()
+      subject.rustSideCancellationHandle?.cancel()
     }
     return (subject.stream, subject.continuation.finish)
   }
@@ -157,16 +164,28 @@ extension AsyncSubject<Transaction>: IsTransactionPublisher {
     print("❌ SWIFT received finishedFromRustSide")
     self.continuation.finish()
   }
-  func rustIsSubscribedNotifyCancellationOn(listener: CancellationListener) {
-    print("🌱 SWIFT rustIsSubscribedNotifyCancellationOn got listener")
-    self.rustSideCancellationListener = listener
+  func rustIsSubscribedNotifyCancellationOn(cancellationHandle: FfiCancellationHandle) {
+    print("🌱 SWIFT rustIsSubscribedNotifyCancellationOn got cancellation handle")
+    self.rustSideCancellationHandle = cancellationHandle
+  }
+}
+
+/// Replays the network requests recorded in the cassette, unless `FFIBRE_RECORD_CASSETTES` is set,
+/// in which case `networkAntenna` makes them against mainnet and they are recorded.
+func cassette(wrapping networkAntenna: FfiNetworkingExecutor) -> FfiNetworkingExecutor {
+  let environment = ProcessInfo.processInfo.environment
+  let path = "\(environment["CARGO_MANIFEST_DIR"]!)/tests/cassettes/latest_transactions.jsonl"
+  if environment["FFIBRE_RECORD_CASSETTES"] != nil {
+    return NetworkingCassette.recording(networkAntenna: networkAntenna, cassettePath: path)
+      .asNetworkAntenna()
   }
+  return try! NetworkingCassette.replaying(cassettePath: path).asNetworkAntenna()
 }
 
 func test_async_stream() async throws {
 
   let gatewayClient = GatewayClient(
-    networkAntenna: URLSession.shared
+    networkAntenna: cassette(wrapping: URLSession.shared)
   )
 
   let t = Task {
@@ -82,10 +82,16 @@ extension FfiNetworkingRequest {
 
 // Conform `[Swift]URLSession` to `[Rust]FfiNetworkingExecutor`
 extension URLSession: FfiNetworkingExecutor {
+  public func networkingCapabilities() -> FfiNetworkingCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeNetworkingRequest(
     request rustRequest: FfiNetworkingRequest,
-    listenerRustSide: FfiNetworkingOutcomeListener
-  ) throws {
+    listenerRustSide: FfiNetworkingOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiNetworkingOutcome? {
     guard let url = URL(string: rustRequest.url) else {
       throw FfiNetworkingError.failedToCreateUrlFrom(string: rustRequest.url)
     }
@@ -95,9 +101,10 @@ extension URLSession: FfiNetworkingExecutor {
         urlResponse: urlResponse,
         error: error
       )
-      listenerRustSide.notifyOutcome(result: result)
+      try? listenerRustSide.notifyOutcome(result: result)
     }
     task.resume()
+    return nil
   }
 }
 
@@ -143,10 +150,22 @@ extension GatewayClient {
   }
 }
 
+/// Replays the network requests recorded in the cassette, unless `FFIBRE_RECORD_CASSETTES` is set,
+/// in which case `networkAntenna` makes them against mainnet and they are recorded.
+func cassette(wrapping networkAntenna: FfiNetworkingExecutor) -> FfiNetworkingExecutor {
+  let environment = ProcessInfo.processInfo.environment
+  let path = "\(environment["CARGO_MANIFEST_DIR"]!)/tests/cassettes/latest_transactions.jsonl"
+  if environment["FFIBRE_RECORD_CASSETTES"] != nil {
+    return NetworkingCassette.recording(networkAntenna: networkAntenna, cassettePath: path)
+      .asNetworkAntenna()
+  }
+  return try! NetworkingCassette.replaying(cassettePath: path).asNetworkAntenna()
+}
+
 func test_async_stream() async throws {
 
   let gatewayClient = GatewayClient(
-    networkAntenna: URLSession.shared
+    networkAntenna: cassette(wrapping: URLSession.shared)
   )
 
   await withDiscardingTaskGroup { taskGroup in
@@ -2,20 +2,46 @@ import uniffi.ffibre.*
 import kotlinx.coroutines.*
 import java.io.*
 import java.nio.file.FileSystems
+import java.nio.file.Files
+import java.nio.file.StandardCopyOption
 import java.security.AccessControlException
+import kotlin.concurrent.thread
 
 object FileWriter: FfiFileIoWriteExecutor {
+    override fun fileIoWriteCapabilities() = FfiFileIoWriteCapabilities(
+        supportsAppend = true,
+        supportsAtomicWrite = true
+    )
+
     override fun executeFileIoWriteRequest(
         request: FfiFileIoWriteRequest,
-        listenerRustSide: FfiFileIoWriteOutcomeListener
-    ) {
+        listenerRustSide: FfiFileIoWriteOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiFileIoWriteOutcome? {
         val response = runCatching {
             val file = File(request.absolutePath)
             val fileExisted = file.exists()
-            if (fileExisted && request.existsStrategy == FileAlreadyExistsStrategy.ABORT) {
+            val strategy = request.existsStrategy
+            if (fileExisted && strategy is FileAlreadyExistsStrategy.Abort) {
                 FfiFileIoWriteResponse.OverwriteAborted
             } else {
-                file.writeBytes(request.contents)
+                val total = request.contents.size.toULong()
+                listenerRustSide.notifyProgress(completed = 0uL, total = total)
+                if (fileExisted && strategy is FileAlreadyExistsStrategy.Append) {
+                    file.appendBytes(strategy.separator.toByteArray() + request.contents)
+                } else if (request.atomic) {
+                    val temporary = File.createTempFile(file.name, ".tmp", file.absoluteFile.parentFile)
+                    temporary.writeBytes(request.contents)
+                    Files.move(
+                        temporary.toPath(),
+                        file.toPath(),
+                        StandardCopyOption.ATOMIC_MOVE,
+                        StandardCopyOption.REPLACE_EXISTING
+                    )
+                } else {
+                    file.writeBytes(request.contents)
+                }
+                listenerRustSide.notifyProgress(completed = total, total = total)
                 FfiFileIoWriteResponse.DidWrite(alreadyExisted = fileExisted)
             }
         }.fold(
@@ -32,36 +58,131 @@ object FileWriter: FfiFileIoWriteExecutor {
         )
 
         listenerRustSide.notifyOutcome(response)
+        return null
     }
 }
 
+fun readFile(request: FfiFileIoReadRequest): FfiFileIoReadOutcome =
+    runCatching {
+        val file = File(request.absolutePath)
+        val range = request.range
+        if (!file.exists()) {
+            FfiFileIoReadResponse.DoesNotExist
+        } else if (range != null) {
+            RandomAccessFile(file, "r").use { handle ->
+                val offset = minOf(range.offset.toLong(), handle.length())
+                val buffer = ByteArray(minOf(range.length.toLong(), handle.length() - offset).toInt())
+                handle.seek(offset)
+                handle.readFully(buffer)
+                FfiFileIoReadResponse.Exists(contents = buffer)
+            }
+        } else {
+            FfiFileIoReadResponse.Exists(contents = file.readBytes())
+        }
+    }.fold(
+        onSuccess = { response ->
+            FfiFileIoReadOutcome.Success(response)
+        },
+        onFailure = { error ->
+            FfiFileIoReadOutcome.Failure(FfiFileIoReadError.Unknown(underlying = error.message.orEmpty()))
+        }
+    )
+
 object FileReader: FfiFileIoReadExecutor {
+    override fun fileIoReadCapabilities() = FfiFileIoReadCapabilities(supportsRangeRead = true)
+
+    override fun executeFileIoReadRequest(
+        request: FfiFileIoReadRequest,
+        listenerRustSide: FfiFileIoReadOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiFileIoReadOutcome? {
+        // Read synchronously, so returned immediately instead of notified.
+        return readFile(request)
+    }
+}
+
+object BundledResourcesReader: FfiFileIoReadExecutor {
+    override fun fileIoReadCapabilities(): FfiFileIoReadCapabilities? = null
+
+    override fun executeFileIoReadRequest(
+        request: FfiFileIoReadRequest,
+        listenerRustSide: FfiFileIoReadOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiFileIoReadOutcome? {
+        val response = FfiFileIoReadResponse.Exists(contents = "Bundled".toByteArray())
+        return FfiFileIoReadOutcome.Success(response)
+    }
+}
+
+object UnmountedDiskReader: FfiFileIoReadExecutor {
+    override fun fileIoReadCapabilities(): FfiFileIoReadCapabilities? = null
+
     override fun executeFileIoReadRequest(
         request: FfiFileIoReadRequest,
-        listenerRustSide: FfiFileIoReadOutcomeListener
+        listenerRustSide: FfiFileIoReadOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiFileIoReadOutcome? {
+        listenerRustSide.notifyOutcome(FfiFileIoReadOutcome.Failure(FfiFileIoReadError.Unknown(underlying = "Disk not mounted")))
+        return null
+    }
+}
+
+object FileBatchReader: FfiFileIoReadBatchExecutor {
+    @Volatile var batches: Int = 0
+
+    override fun executeFileIoReadBatchRequest(
+        requests: List<FfiFileIoReadRequest>,
+        listenerRustSide: FfiFileIoReadBatchOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
     ) {
-        val response = runCatching {
+        batches += 1
+        listenerRustSide.notifyOutcomes(requests.map { readFile(it) })
+    }
+}
+
+object FileChunkReader: FfiFileIoReadChunksExecutor {
+    override fun executeFileIoReadChunksRequest(
+        request: FfiFileIoReadChunksRequest,
+        listenerRustSide: FfiFileIoReadChunksStreamListener,
+        cancellationHandle: FfiCancellationHandle
+    ) {
+        // `notifyValueAsync` suspends until Rust has room for the chunk, so read on another thread.
+        thread {
             val file = File(request.absolutePath)
-            if (file.exists()) {
-                FfiFileIoReadResponse.Exists(contents = file.readBytes())
-            } else {
-                FfiFileIoReadResponse.DoesNotExist
+            if (!file.exists()) {
+                listenerRustSide.notifyFailed(FfiFileIoReadChunksError.DoesNotExist)
+                return@thread
             }
-        }.fold(
-            onSuccess = { response ->
-                FfiFileIoReadOutcome.Success(response)
-            },
-            onFailure = { error ->
-                FfiFileIoReadOutcome.Failure(FfiFileIoReadError.Unknown(underlying = error.message.orEmpty()))
-            }
-        )
-
-        listenerRustSide.notifyOutcome(response)
+            runCatching {
+                file.inputStream().use { input ->
+                    val buffer = ByteArray(request.chunkSize.toInt())
+                    while (!cancellationHandle.isCancelled()) {
+                        val count = input.read(buffer)
+                        if (count < 0) break
+                        runBlocking { listenerRustSide.notifyValueAsync(buffer.copyOf(count)) }
+                    }
+                }
+            }.fold(
+                onSuccess = { listenerRustSide.notifyCompleted() },
+                onFailure = { error ->
+                    listenerRustSide.notifyFailed(FfiFileIoReadChunksError.Unknown(underlying = error.message.orEmpty()))
+                }
+            )
+        }
     }
 }
 
 
 
+class PrintProgress: FfiOperationProgressObserver {
+    @Volatile var completed: ULong = 0uL
+
+    override fun onProgress(progress: FfiOperationProgress) {
+        completed = progress.completed
+        println("⏳🗂️  write progress: ${progress.completed}/${progress.total}")
+    }
+}
+
 suspend fun write(path: String, content: String, strategy: ExtendExistingFileStrategy) {
     val fileInterface = FileIoInterface(fileWriter = FileWriter, fileReader = FileReader)
 
@@ -82,20 +203,73 @@ fun test() = runBlocking {
     fileInterface.write(
         fileAbsolutePath = path,
         contents = "Kotlin".toByteArray(),
-        existsStrategy = FileAlreadyExistsStrategy.OVERWRITE,
+        existsStrategy = FileAlreadyExistsStrategy.Overwrite,
     )
 
-    fileInterface.writeToNewOrExtendExistingFile(
+    val progress = PrintProgress()
+    fileInterface.writeToNewOrExtendExistingFileWithProgress(
         fileAbsolutePath = path,
         contents = "Hello from".toByteArray(),
-        extendStrategy = ExtendExistingFileStrategy.Prepend(separator = ": ")
+        extendStrategy = ExtendExistingFileStrategy.Prepend(separator = ": "),
+        progressObserver = progress
     )
+    assert(progress.completed == "Hello from: Kotlin".length.toULong())
 
     val content = fileInterface.read(fileAbsolutePath = path)?.toString(charset = Charsets.UTF_8)
     println("Content of file: $path")
     println(content)
     assert("Hello from: Kotlin" == content)
 
+    val greeting = fileInterface.readRange(
+        fileAbsolutePath = path,
+        range = FfiByteRange(offset = 0uL, length = 5uL)
+    )?.toString(charset = Charsets.UTF_8)
+    assert("Hello" == greeting)
+
+    fileInterface.writeToNewOrExtendExistingFile(
+        fileAbsolutePath = path,
+        extendStrategy = ExtendExistingFileStrategy.Append(separator = "!"),
+        contents = "!".toByteArray()
+    )
+    assert("Hello from: Kotlin!!" == fileInterface.read(fileAbsolutePath = path)?.toString(charset = Charsets.UTF_8))
+    fileInterface.write(
+        fileAbsolutePath = path,
+        contents = content!!.toByteArray(),
+        existsStrategy = FileAlreadyExistsStrategy.Overwrite,
+    )
+
+    val chunkedContent = ChunkedFileReader(fileChunkReader = FileChunkReader)
+        .read(fileAbsolutePath = path, chunkSize = 4uL)
+        .toString(charset = Charsets.UTF_8)
+    assert(content == chunkedContent)
+
+    val blockingChunkedContent = ChunkedFileReader(fileChunkReader = FileChunkReader)
+        .readBlocking(fileAbsolutePath = path, chunkSize = 4uL)
+        .toString(charset = Charsets.UTF_8)
+    assert(content == blockingChunkedContent)
+
+    val missingPath = "$path.missing"
+    fileInterface.setReadBatchExecutor(batchExecutor = FileBatchReader)
+    val contents = fileInterface.readFiles(fileAbsolutePaths = listOf(path, missingPath))
+    assert(FileBatchReader.batches == 1)
+    assert((contents[0] as FileContentsResult.Success).contents?.toString(charset = Charsets.UTF_8) == content)
+    assert((contents[1] as FileContentsResult.Success).contents == null)
+
+    val fallbackInterface = FileIoInterface.withFallbackExecutors(
+        fileWriter = FileWriter,
+        fileReader = UnmountedDiskReader,
+        fallbackFileWriters = listOf(),
+        fallbackFileReaders = listOf(BundledResourcesReader),
+        configuration = DispatcherConfiguration(fallbackOn = listOf(FallbackTrigger.HOST_FAILURE))
+    )
+    val served = fallbackInterface.readServed(fileAbsolutePath = path)
+    assert(served.servedBy == 1u)
+    assert("Bundled" == served.contents?.toString(charset = Charsets.UTF_8))
+
+    val readStats = fileInterface.readStats()
+    assert(readStats.dispatched == readStats.succeeded)
+    println("📊🗂️  read stats: $readStats")
+
     println("🚀🗂️  Kotlin 'test_file_io' done")
 }
 
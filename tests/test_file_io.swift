@@ -111,20 +111,27 @@ where Request == FfiFileIoReadRequest, Intermediary == Data?, Response == FfiFil
     )
   }
 
+  public func fileIoReadCapabilities() -> FfiFileIoReadCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeFileIoReadRequest(
     request rustRequest: FfiFileIoReadRequest,
-    listenerRustSide: FfiFileIoReadOutcomeListener
-  ) throws {
+    listenerRustSide: FfiFileIoReadOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiFileIoReadOutcome? {
     self.task = Task {
       do {
         let intermediary = try await self.operation(rustRequest)
         let response = try await self.mapToResponse(intermediary)
-        listenerRustSide.notifyOutcome(result: .success(value: response))
+        try? listenerRustSide.notifyOutcome(result: .success(value: response))
       } catch {
-        listenerRustSide.notifyOutcome(
+        try? listenerRustSide.notifyOutcome(
           result: .failure(error: .unknown(underlying: String(describing: error))))
       }
     }
+    return nil
   }
 }
 
@@ -146,23 +153,30 @@ where
     )
   }
 
+  public func fileIoWriteCapabilities() -> FfiFileIoWriteCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeFileIoWriteRequest(
     request rustRequest: Request,
-    listenerRustSide: FfiFileIoWriteOutcomeListener
-  ) throws {
+    listenerRustSide: FfiFileIoWriteOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiFileIoWriteOutcome? {
     self.task = Task {
       do {
         let intermediary = try await self.operation(rustRequest)
         let response = try await self.mapToResponse(intermediary)
-        listenerRustSide.notifyOutcome(result: .success(value: response))
+        try? listenerRustSide.notifyOutcome(result: .success(value: response))
       } catch let writeError as FfiFileIoWriteError {
-        listenerRustSide.notifyOutcome(
+        try? listenerRustSide.notifyOutcome(
           result: .failure(error: writeError)
         )
       } catch {
         fatalError("Expected all errors to be of type FfiFileIoWriteError")
       }
     }
+    return nil
   }
 }
 
@@ -204,13 +218,24 @@ extension CallbackBasedFileManager: FfiFileIoReadExecutor {
     }
   }
 
+  public func fileIoReadCapabilities() -> FfiFileIoReadCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeFileIoReadRequest(
     request: FfiFileIoReadRequest,
-    listenerRustSide: FfiFileIoReadOutcomeListener
-  ) throws {
+    listenerRustSide: FfiFileIoReadOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiFileIoReadOutcome? {
+    // Known without touching the disk, so returned immediately.
+    guard FileManager.default.fileExists(atPath: request.absolutePath) else {
+      return .success(value: .doesNotExist)
+    }
     self.read(absolutePath: request.absolutePath) { result in
-      listenerRustSide.notifyOutcome(result: result)
+      try? listenerRustSide.notifyOutcome(result: result)
     }
+    return nil
   }
 }
 
@@ -250,17 +275,24 @@ extension CallbackBasedFileManager: FfiFileIoWriteExecutor {
     }
 
   }
+  public func fileIoWriteCapabilities() -> FfiFileIoWriteCapabilities? {
+    // Unknown, Rust assumes the least.
+    nil
+  }
+
   public func executeFileIoWriteRequest(
     request: FfiFileIoWriteRequest,
-    listenerRustSide: FfiFileIoWriteOutcomeListener
-  ) throws {
+    listenerRustSide: FfiFileIoWriteOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiFileIoWriteOutcome? {
     self.write(
       contents: request.contents,
       to: request.absolutePath,
       abortIfExists: request.existsStrategy == .abort
     ) { result in
-      listenerRustSide.notifyOutcome(result: result)
+      try? listenerRustSide.notifyOutcome(result: result)
     }
+    return nil
   }
 }
 
@@ -3,11 +3,31 @@ import kotlinx.coroutines.*
 import okhttp3.*
 import okhttp3.MediaType.Companion.toMediaType
 import okhttp3.Headers.Companion.toHeaders
+import java.util.concurrent.TimeUnit
+
+// Runs `action` when Rust side cancels an operation, i.e. no longer awaits its outcome.
+class OnCancellation(private val action: () -> Unit): FfiCancellationObserver {
+    override fun onCancelled() = action()
+}
 
 object KotlinNetworkAntenna: FfiNetworkingExecutor {
     private val client = OkHttpClient()
 
-    override fun executeNetworkingRequest(request: FfiNetworkingRequest, listenerRustSide: FfiNetworkingOutcomeListener) {
+    override fun networkingCapabilities() = FfiNetworkingCapabilities(
+        supportedMethods = listOf("GET", "POST", "PUT", "PATCH", "DELETE"),
+        maxBodySize = null
+    )
+
+    override fun executeNetworkingRequest(
+        request: FfiNetworkingRequest,
+        listenerRustSide: FfiNetworkingOutcomeListener,
+        cancellationHandle: FfiCancellationHandle
+    ): FfiNetworkingOutcome? {
+        // Rust side stops awaiting the response at the deadline, so should we.
+        val callClient = request.deadline?.let {
+            client.newBuilder().callTimeout(it.timeoutMs.toLong(), TimeUnit.MILLISECONDS).build()
+        } ?: client
+
         val outcome = runCatching {
             val contentType = request.headers["Content-Type"] ?: "application/json; charset=utf-8"
 
@@ -21,7 +41,9 @@ object KotlinNetworkAntenna: FfiNetworkingExecutor {
                 .method(method = request.method, body = requestBody)
                 .build()
 
-            client.newCall(request).execute()
+            val call = callClient.newCall(request)
+            cancellationHandle.registerObserver(observer = OnCancellation { call.cancel() })
+            call.execute()
         }.fold(
             onSuccess = { response ->
                 if (response.isSuccessful) {
@@ -53,13 +75,25 @@ object KotlinNetworkAntenna: FfiNetworkingExecutor {
         )
 
         listenerRustSide.notifyOutcome(result = outcome)
+        return null
+    }
+}
+
+// Replays the network requests recorded in the cassette, unless `FFIBRE_RECORD_CASSETTES` is set,
+// in which case `networkAntenna` makes them against mainnet and they are recorded.
+fun cassette(networkAntenna: FfiNetworkingExecutor): FfiNetworkingExecutor {
+    val path = "${System.getenv("CARGO_MANIFEST_DIR")}/tests/cassettes/gateway.jsonl"
+    return if (System.getenv("FFIBRE_RECORD_CASSETTES") != null) {
+        NetworkingCassette.recording(networkAntenna = networkAntenna, cassettePath = path).asNetworkAntenna()
+    } else {
+        NetworkingCassette.replaying(cassettePath = path).asNetworkAntenna()
     }
 }
 
 suspend fun testBalance(address: String) = runCatching {
     println("🛜 ┌ Test Balance")
     println("🛜 ┝ Request for $address")
-    val client = GatewayClient(networkAntenna = KotlinNetworkAntenna)
+    val client = GatewayClient(networkAntenna = cassette(KotlinNetworkAntenna))
     client.getXrdBalanceOfAccount(address = address)
 }.onSuccess { balance ->
     println("🛜 ┝ $balance ")
@@ -70,7 +104,7 @@ suspend fun testBalance(address: String) = runCatching {
 
 suspend fun testLatestTransactions() = runCatching {
     println("🛜 ┌ Test Latest Transactions")
-    val client = GatewayClient(networkAntenna = KotlinNetworkAntenna)
+    val client = GatewayClient(networkAntenna = cassette(KotlinNetworkAntenna))
     client.getLatestTransactions()
 }.onSuccess { transactions ->
      println("${transactions.joinToString(prefix = "🛜 ┝ ", separator = "\n🛜 ┝ ")}")
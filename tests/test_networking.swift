@@ -69,6 +69,14 @@ extension FfiNetworkingRequest {
     request.httpMethod = self.method
     request.httpBody = self.body
     request.allHTTPHeaderFields = self.headers
+    if let operationId = self.operationId {
+      // Lets us correlate gateway logs with Rust side logs.
+      request.setValue(String(operationId), forHTTPHeaderField: "X-Operation-Id")
+    }
+    if let deadline = self.deadline {
+      // Rust side stops awaiting the response at the deadline, so should we.
+      request.timeoutInterval = TimeInterval(deadline.timeoutMs) / 1000
+    }
     return request
   }
 
@@ -80,24 +88,70 @@ extension FfiNetworkingRequest {
   }
 }
 
+// Runs `action` when Rust side cancels an operation, i.e. no longer awaits its outcome.
+final class OnCancellation: FfiCancellationObserver {
+  private let action: () -> Void
+  init(_ action: @escaping () -> Void) {
+    self.action = action
+  }
+  func onCancelled() {
+    action()
+  }
+}
+
 // Conform `[Swift]URLSession` to `[Rust]FfiNetworkingExecutor`
 extension URLSession: FfiNetworkingExecutor {
+  public func networkingCapabilities() -> FfiNetworkingCapabilities? {
+    FfiNetworkingCapabilities(
+      supportedMethods: ["GET", "POST", "PUT", "PATCH", "DELETE"],
+      maxBodySize: nil
+    )
+  }
+
   public func executeNetworkingRequest(
     request rustRequest: FfiNetworkingRequest,
-    listenerRustSide: FfiNetworkingOutcomeListener
-  ) throws {
+    listenerRustSide: FfiNetworkingOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiNetworkingOutcome? {
     guard let url = URL(string: rustRequest.url) else {
       throw FfiNetworkingError.failedToCreateUrlFrom(string: rustRequest.url)
     }
+    let operationId = listenerRustSide.operationId()
     let task = dataTask(with: rustRequest.urlRequest(url: url)) { data, urlResponse, error in
+      print("🛜 SWIFT URLSession finished operation #\(operationId)")
       let result = FfiNetworkingOutcome.with(
         data: data,
         urlResponse: urlResponse,
         error: error
       )
-      listenerRustSide.notifyOutcome(result: result)
+      try? listenerRustSide.notifyOutcome(result: result)
     }
     task.resume()
+    cancellationHandle.registerObserver(observer: OnCancellation { task.cancel() })
+    return nil
+  }
+}
+
+// Conform to `[Rust]FfiNetworkingInterceptor` to inject a header into, and log, every request.
+final class UserAgentInterceptor: FfiNetworkingInterceptor {
+  func interceptNetworkingRequest(request: FfiNetworkingRequest) -> FfiNetworkingRequest {
+    var request = request
+    request.headers["User-Agent"] = "FFiBrE"
+    return request
+  }
+  func interceptNetworkingOutcome(outcome: FfiNetworkingOutcome) {
+    switch outcome {
+    case let .success(value): print("🛜 🔎 SWIFT intercepted response, status code: \(value.statusCode)")
+    case let .failure(error): print("🛜 🔎 SWIFT intercepted failure: \(error)")
+    }
+  }
+}
+
+// Conform to `[Rust]FfiLogSink` to route Rust's logs into e.g. `os_log`, here we just print them.
+final class PrintLogSink: FfiLogSink {
+  func log(record: FfiLogRecord) {
+    let fields = record.fields.sorted { $0.key < $1.key }.map { "\($0.key)=\($0.value)" }.joined(separator: " ")
+    print("🦀 \(record.level) [\(record.target)] \(record.message) \(fields)")
   }
 }
 
@@ -136,25 +190,47 @@ where
     )
   }
 
+  public func networkingCapabilities() -> FfiNetworkingCapabilities? {
+    FfiNetworkingCapabilities(
+      supportedMethods: ["GET", "POST", "PUT", "PATCH", "DELETE"],
+      maxBodySize: nil
+    )
+  }
+
   public func executeNetworkingRequest(
     request rustRequest: FfiNetworkingRequest,
-    listenerRustSide: FfiNetworkingOutcomeListener
-  ) throws {
+    listenerRustSide: FfiNetworkingOutcomeListener,
+    cancellationHandle: FfiCancellationHandle
+  ) throws -> FfiNetworkingOutcome? {
     self.task = Task {
       do {
         let intermediary = try await self.operation(rustRequest)
         let response = try await self.mapToResponse(intermediary)
-        listenerRustSide.notifyOutcome(result: .success(value: response))
+        try? listenerRustSide.notifyOutcome(result: .success(value: response))
       } catch {
-        listenerRustSide.notifyOutcome(result: .fail(error: error))
+        try? listenerRustSide.notifyOutcome(result: .fail(error: error))
       }
     }
+    cancellationHandle.registerObserver(observer: OnCancellation { [task = self.task] in task?.cancel() })
+    return nil
   }
 }
 
+/// Replays the network requests recorded in the cassette, unless `FFIBRE_RECORD_CASSETTES` is set,
+/// in which case `networkAntenna` makes them against mainnet and they are recorded.
+func cassette(wrapping networkAntenna: FfiNetworkingExecutor) -> FfiNetworkingExecutor {
+  let environment = ProcessInfo.processInfo.environment
+  let path = "\(environment["CARGO_MANIFEST_DIR"]!)/tests/cassettes/gateway.jsonl"
+  if environment["FFIBRE_RECORD_CASSETTES"] != nil {
+    return NetworkingCassette.recording(networkAntenna: networkAntenna, cassettePath: path)
+      .asNetworkAntenna()
+  }
+  return try! NetworkingCassette.replaying(cassettePath: path).asNetworkAntenna()
+}
+
 func test_callback(address: String) async throws {
   let gatewayClient = GatewayClient(
-    networkAntenna: URLSession.shared
+    networkAntenna: cassette(wrapping: URLSession.shared)
   )
 
   let balance = try await gatewayClient.getXrdBalanceOfAccount(address: address)
@@ -162,23 +238,114 @@ func test_callback(address: String) async throws {
 }
 
 func test_async(address: String) async throws {
-  let gatewayClient = GatewayClient(
-    networkAntenna: Async(call: URLSession.shared.data(for:))
+  let gatewayClient = GatewayClient.withConfiguration(
+    networkAntenna: cassette(wrapping: Async(call: URLSession.shared.data(for:))),
+    configuration: DispatcherConfiguration(
+      timeoutMs: 30_000,
+      retryPolicy: RetryPolicy(
+        maxAttempts: 3,
+        initialBackoffMs: 250,
+        maxBackoffMs: 5_000,
+        backoffMultiplier: 2,
+        jitter: 0.5
+      )
+    )
   )
 
+  gatewayClient.addInterceptor(interceptor: UserAgentInterceptor())
+
   let balance = try await gatewayClient.getXrdBalanceOfAccount(address: address)
   print("🛜 ✅ SWIFT ASYNC balance: \(balance)")
+
+  let stats = gatewayClient.stats()
+  assert(stats.dispatched >= 1 && stats.inFlight == 0)
+  print("🛜 📊 SWIFT ASYNC stats: \(stats)")
+}
+
+func test_replace_executor(address: String) async throws {
+  let gatewayClient = GatewayClient(networkAntenna: cassette(wrapping: URLSession.shared))
+  _ = try await gatewayClient.getXrdBalanceOfAccount(address: address)
+
+  // E.g. after login, switch to a session with another configuration, without rebuilding the client.
+  let configuration = URLSessionConfiguration.ephemeral
+  configuration.httpAdditionalHeaders = ["X-Replaced-Executor": "true"]
+  gatewayClient.replaceExecutor(
+    networkAntenna: cassette(wrapping: URLSession(configuration: configuration))
+  )
+
+  let balance = try await gatewayClient.getXrdBalanceOfAccount(address: address)
+  print("🛜 ✅ SWIFT replaced executor balance: \(balance)")
+}
+
+func test_many_balances(address: String) async throws {
+  let gatewayClient = GatewayClient.withConfiguration(
+    networkAntenna: cassette(wrapping: Async(call: URLSession.shared.data(for:))),
+    configuration: DispatcherConfiguration(maxInFlight: 2)
+  )
+
+  let balances = try await gatewayClient.getXrdBalancesOfAccounts(
+    addresses: Array(repeating: address, count: 5)
+  )
+  assert(balances.count == 5)
+  assert(gatewayClient.queueDepth() == 0)
+  print("🛜 ✅ SWIFT ASYNC balances: \(balances)")
+}
+
+func test_coalesced_balances(address: String) async throws {
+  let gatewayClient = GatewayClient.withConfiguration(
+    networkAntenna: cassette(wrapping: Async(call: URLSession.shared.data(for:))),
+    configuration: DispatcherConfiguration(coalesceRequests: true)
+  )
+
+  // Made concurrently for the same address, so they share one network request.
+  async let first = gatewayClient.getXrdBalanceOfAccount(address: address)
+  async let second = gatewayClient.getXrdBalanceOfAccount(address: address)
+  async let third = gatewayClient.getXrdBalanceOfAccount(address: address)
+  let balances = try await [first, second, third]
+  assert(Set(balances).count == 1)
+  print("🛜 ✅ SWIFT ASYNC coalesced balances: \(balances)")
+}
+
+func test_circuit_breaker(address: String) async throws {
+  let gatewayClient = GatewayClient.withConfiguration(
+    networkAntenna: Async(call: { _ in throw URLError(.notConnectedToInternet) }),
+    configuration: DispatcherConfiguration(
+      circuitBreaker: CircuitBreakerPolicy(
+        failureRateThreshold: 0.5,
+        windowSize: 4,
+        minimumOperations: 2,
+        openDurationMs: 60_000,
+        halfOpenTrialOperations: 1
+      )
+    )
+  )
+
+  for _ in 0..<2 {
+    _ = try? await gatewayClient.getXrdBalanceOfAccount(address: address)
+  }
+  assert(gatewayClient.circuitState() == .open)
+
+  do {
+    _ = try await gatewayClient.getXrdBalanceOfAccount(address: address)
+    assertionFailure("Expected the open circuit to fail fast")
+  } catch FfiBridgeError.FromRust(.CircuitOpen, _) {
+    print("🛜 ✅ SWIFT circuit open, showing offline banner")
+  }
 }
 
 func test_balance() async throws {
   let address = "account_rdx16xlfcpp0vf7e3gqnswv8j9k58n6rjccu58vvspmdva22kf3aplease"
   try await test_callback(address: address)
   try await test_async(address: address)
+  try await test_many_balances(address: address)
+  try await test_replace_executor(address: address)
+  try await test_coalesced_balances(address: address)
+  try await test_circuit_breaker(address: address)
 }
 
 func test_latest_tx() async throws {
   let gatewayClient = GatewayClient(
-    networkAntenna: Async(call: URLSession.shared.data(for:))
+    networkAntenna: cassette(wrapping: Async(call: URLSession.shared.data(for:)))
   )
   let transactions = try await gatewayClient.getLatestTransactions()
   let transactionsDescription = transactions.map { String(describing: $0) }.joined(separator: ", ")
@@ -190,6 +357,7 @@ func test_latest_tx() async throws {
 func test() async throws {
   print("🚀🛜  SWIFT 'test_networking' start")
   defer { print("🏁🛜  SWIFT 'test_networking' done") }
+  installLogSink(sink: PrintLogSink(), minLevel: .debug)
 
   do {
     try await test_balance()